@@ -0,0 +1,340 @@
+//! Per-host adaptive tuning: learns a rolling round-trip-time estimate for
+//! each scanned host and derives a tighter timeout from it, instead of using
+//! one fixed `default_timeout` for every target regardless of how close or
+//! distant it is.
+//!
+//! `AdaptiveLearning::learn_from_scan` is fed one `ScanLearningData` summary
+//! per completed host scan and updates an exponentially weighted moving
+//! average (EWMA) of observed latency for that host. Once enough samples
+//! have accumulated (`AdaptiveConfig::min_scans_for_optimization`),
+//! `get_optimal_params` derives `clamp(k * rtt_ewma, floor, default_timeout)`
+//! as the adaptive timeout - tight enough to speed up scans of nearby hosts,
+//! but never above what the caller considers safe.
+//!
+//! The RTT samples fed in here are, where available, the kernel's `TCP_INFO`
+//! smoothed RTT rather than a wall-clock measurement of the whole probe -
+//! `scanner.rs` reads it off the connected socket for `Connect` scans on
+//! Linux and falls back to wall-clock timing everywhere else (other
+//! platforms, UDP, raw SYN/FIN/XMAS/NULL).
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use crate::config::AdaptiveConfig;
+
+/// Lower bound on the adaptive timeout, so a handful of suspiciously fast
+/// samples can't tune a host down to an unusably small timeout.
+const TIMEOUT_FLOOR_MS: u64 = 50;
+
+/// Multiple of the RTT EWMA used as the per-port timeout, to tolerate normal
+/// jitter rather than the rare slow probe.
+const TIMEOUT_MULTIPLIER: f64 = 4.0;
+
+/// Coarse network locality classification, used to reason about a host's
+/// expected latency independent of its learned history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetworkType {
+    Loopback,
+    Private,
+    Public,
+}
+
+/// Classify a target IP by its network locality.
+pub fn classify_network(ip: IpAddr) -> NetworkType {
+    match ip {
+        IpAddr::V4(ipv4) => {
+            if ipv4.is_loopback() {
+                NetworkType::Loopback
+            } else if ipv4.is_private() {
+                NetworkType::Private
+            } else {
+                NetworkType::Public
+            }
+        }
+        IpAddr::V6(ipv6) => {
+            if ipv6.is_loopback() {
+                NetworkType::Loopback
+            } else {
+                match ipv6.segments()[0] {
+                    0xfe80 | 0xfc00 | 0xfd00 => NetworkType::Private,
+                    _ => NetworkType::Public,
+                }
+            }
+        }
+    }
+}
+
+/// Outcome of a single scanned port, summarized for the learning step.
+#[derive(Debug, Clone)]
+pub struct PortScanResult {
+    pub port: u16,
+    pub is_open: bool,
+    pub is_filtered: bool,
+    pub response_time: Option<f64>,
+    pub service_detected: Option<String>,
+    // Kernel-reported retransmit count for this connection, when available
+    // (`Connect` scans on Linux, via `TCP_INFO`).
+    pub retransmits: Option<u32>,
+}
+
+/// Summary of one completed host scan, fed to `AdaptiveLearning::learn_from_scan`.
+#[derive(Debug, Clone)]
+pub struct ScanLearningData {
+    pub target: IpAddr,
+    pub network_type: NetworkType,
+    pub port_results: Vec<PortScanResult>,
+    pub scan_duration: Duration,
+    pub avg_response_time: f64,
+    pub timeout_rate: f64,
+    pub parallelism_used: u16,
+    pub rate_limit_used: u64,
+    pub scan_performance: f64,
+}
+
+/// Adaptive parameters derived from a host's learned history. A value of `0`
+/// in any field means "not yet optimized" - the caller should fall back to
+/// its own default for that parameter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OptimalParams {
+    pub timeout: u64,
+    pub rate_limit: u64,
+    pub parallelism: u16,
+}
+
+#[derive(Debug, Clone, Default)]
+struct HostIntelligence {
+    rtt_ewma_ms: Option<f64>,
+    scan_count: u32,
+}
+
+/// Learns and serves per-host adaptive scan parameters.
+#[derive(Debug, Clone)]
+pub struct AdaptiveLearning {
+    learning_rate: f64,
+    min_scans_for_optimization: u32,
+    hosts: HashMap<IpAddr, HostIntelligence>,
+    // How often each port has come back open, per network class - feeds
+    // `ordered_ports_for` so `ScanOrder::Adaptive` can probe the ports most
+    // likely to be open first instead of in the caller's given order.
+    open_port_counts: HashMap<NetworkType, HashMap<u16, u32>>,
+}
+
+impl AdaptiveLearning {
+    pub fn new() -> Self {
+        Self {
+            learning_rate: 0.1,
+            min_scans_for_optimization: 5,
+            hosts: HashMap::new(),
+            open_port_counts: HashMap::new(),
+        }
+    }
+
+    /// Apply `learning_rate`/`min_scans_for_optimization` from a (possibly
+    /// hot-reloaded) `AdaptiveConfig`, without touching learned history.
+    pub fn configure(&mut self, config: &AdaptiveConfig) {
+        self.learning_rate = config.learning_rate;
+        self.min_scans_for_optimization = config.min_scans_for_optimization;
+    }
+
+    /// Derive adaptive parameters for `target`, given the caller's own
+    /// `default_timeout` as the ceiling the adaptive value is clamped under.
+    pub fn get_optimal_params(&self, target: IpAddr, default_timeout: u64) -> OptimalParams {
+        let Some(host) = self.hosts.get(&target) else {
+            return OptimalParams::default();
+        };
+
+        if host.scan_count < self.min_scans_for_optimization {
+            return OptimalParams::default();
+        }
+
+        let timeout = match host.rtt_ewma_ms {
+            Some(rtt_ewma) => {
+                let scaled = (rtt_ewma * TIMEOUT_MULTIPLIER).round() as u64;
+                scaled.clamp(TIMEOUT_FLOOR_MS, default_timeout.max(TIMEOUT_FLOOR_MS))
+            }
+            None => 0,
+        };
+
+        OptimalParams { timeout, rate_limit: 0, parallelism: 0 }
+    }
+
+    /// Fold one completed scan's results into the per-host RTT EWMA.
+    ///
+    /// Only ports that actually produced a reply (open, or closed via a
+    /// reset) count as RTT samples - a filtered port's `response_time` is
+    /// just however long it took to time out, which would drag the estimate
+    /// toward the very timeout we're trying to shrink.
+    pub fn learn_from_scan(&mut self, data: &ScanLearningData) {
+        let host = self.hosts.entry(data.target).or_default();
+        host.scan_count += 1;
+
+        let samples: Vec<f64> = data
+            .port_results
+            .iter()
+            .filter(|p| !p.is_filtered)
+            .filter_map(|p| p.response_time)
+            .collect();
+
+        if samples.is_empty() {
+            return;
+        }
+
+        let sample_avg = samples.iter().sum::<f64>() / samples.len() as f64;
+
+        host.rtt_ewma_ms = Some(match host.rtt_ewma_ms {
+            Some(ewma) => (1.0 - self.learning_rate) * ewma + self.learning_rate * sample_avg,
+            None => sample_avg,
+        });
+
+        let counts = self.open_port_counts.entry(data.network_type).or_default();
+        for port_result in &data.port_results {
+            if port_result.is_open {
+                *counts.entry(port_result.port).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Reorder `ports` so ones historically seen open on `network_type` come
+    /// first, highest open-count first, falling back to the given order for
+    /// ports with no history (including the case where there's no history
+    /// at all yet).
+    pub fn ordered_ports_for(&self, network_type: NetworkType, ports: &[u16]) -> Vec<u16> {
+        let Some(counts) = self.open_port_counts.get(&network_type) else {
+            return ports.to_vec();
+        };
+
+        let mut ordered: Vec<u16> = ports.to_vec();
+        ordered.sort_by(|a, b| {
+            let count_a = counts.get(a).copied().unwrap_or(0);
+            let count_b = counts.get(b).copied().unwrap_or(0);
+            count_b.cmp(&count_a)
+        });
+        ordered
+    }
+}
+
+impl Default for AdaptiveLearning {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn learning_data(target: IpAddr, response_times: &[Option<f64>], filtered: &[bool]) -> ScanLearningData {
+        let port_results = response_times
+            .iter()
+            .zip(filtered)
+            .enumerate()
+            .map(|(i, (rt, is_filtered))| PortScanResult {
+                port: i as u16,
+                is_open: rt.is_some() && !is_filtered,
+                is_filtered: *is_filtered,
+                response_time: *rt,
+                service_detected: None,
+                retransmits: None,
+            })
+            .collect();
+
+        ScanLearningData {
+            target,
+            network_type: classify_network(target),
+            port_results,
+            scan_duration: Duration::from_millis(10),
+            avg_response_time: 10.0,
+            timeout_rate: 0.0,
+            parallelism_used: 10,
+            rate_limit_used: 0,
+            scan_performance: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_falls_back_to_default_before_min_scans() {
+        let mut adaptive = AdaptiveLearning::new();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        for _ in 0..4 {
+            adaptive.learn_from_scan(&learning_data(ip, &[Some(5.0)], &[false]));
+        }
+
+        let params = adaptive.get_optimal_params(ip, 1000);
+        assert_eq!(params.timeout, 0);
+    }
+
+    #[test]
+    fn test_derives_clamped_timeout_after_min_scans() {
+        let mut adaptive = AdaptiveLearning::new();
+        adaptive.configure(&AdaptiveConfig {
+            enabled: true,
+            learning_rate: 0.5,
+            min_scans_for_optimization: 3,
+            max_port_intelligence_entries: 1000,
+            max_host_intelligence_entries: 1000,
+            data_retention_days: 30,
+        });
+        let ip: IpAddr = "10.0.0.2".parse().unwrap();
+
+        for _ in 0..3 {
+            adaptive.learn_from_scan(&learning_data(ip, &[Some(10.0)], &[false]));
+        }
+
+        let params = adaptive.get_optimal_params(ip, 1000);
+        assert!(params.timeout >= TIMEOUT_FLOOR_MS);
+        assert!(params.timeout <= 1000);
+        assert!((params.timeout as f64) < 1000.0);
+    }
+
+    #[test]
+    fn test_ordered_ports_front_loads_historically_open_ports() {
+        let mut adaptive = AdaptiveLearning::new();
+        let ip: IpAddr = "10.0.0.4".parse().unwrap();
+
+        // Port 22 comes back open every time, port 80/443 never do.
+        let scan = ScanLearningData {
+            target: ip,
+            network_type: classify_network(ip),
+            port_results: vec![
+                PortScanResult { port: 22, is_open: true, is_filtered: false, response_time: Some(5.0), service_detected: None, retransmits: None },
+                PortScanResult { port: 80, is_open: false, is_filtered: false, response_time: Some(5.0), service_detected: None, retransmits: None },
+                PortScanResult { port: 443, is_open: false, is_filtered: false, response_time: Some(5.0), service_detected: None, retransmits: None },
+            ],
+            scan_duration: Duration::from_millis(10),
+            avg_response_time: 5.0,
+            timeout_rate: 0.0,
+            parallelism_used: 10,
+            rate_limit_used: 0,
+            scan_performance: 1.0,
+        };
+        for _ in 0..5 {
+            adaptive.learn_from_scan(&scan);
+        }
+
+        let ordered = adaptive.ordered_ports_for(NetworkType::Private, &[80, 443, 22]);
+        assert_eq!(ordered[0], 22);
+    }
+
+    #[test]
+    fn test_ordered_ports_falls_back_to_given_order_without_history() {
+        let adaptive = AdaptiveLearning::new();
+        let ports = vec![80, 443, 22];
+        assert_eq!(adaptive.ordered_ports_for(NetworkType::Public, &ports), ports);
+    }
+
+    #[test]
+    fn test_filtered_ports_are_not_counted_as_rtt_samples() {
+        let mut adaptive = AdaptiveLearning::new();
+        let ip: IpAddr = "10.0.0.3".parse().unwrap();
+
+        for _ in 0..10 {
+            adaptive.learn_from_scan(&learning_data(ip, &[Some(1000.0)], &[true]));
+        }
+
+        let params = adaptive.get_optimal_params(ip, 1000);
+        // Every sample was filtered, so no RTT estimate should ever form.
+        assert_eq!(params.timeout, 0);
+    }
+}
@@ -7,7 +7,7 @@ use std::path::PathBuf;
 #[command(version = "0.1.0")]
 #[command(about = "High-performance, secure port scanner with modern features", long_about = None)]
 pub struct Cli {
-    #[arg(short, long, help = "Target IP, hostname, IP range (IP1-IP2), or CIDR (192.168.1.0/24). Can be specified multiple times.")]
+    #[arg(short, long, help = "Target IP, hostname, IP range (IP1-IP2), CIDR (192.168.1.0/24), or a Unix domain socket path (unix:/run/foo.sock). Can be specified multiple times.")]
     pub target: Vec<String>,
     
     #[arg(short, long, default_value = "common", help = "Ports to scan (e.g., 80, 1-1000, 80,443,8080, top100, common, web, mail, db). Can be specified multiple times.")]
@@ -25,8 +25,8 @@ pub struct Cli {
     #[arg(long, default_value = "10", help = "Number of parallel host scans")]
     pub parallel_hosts: usize,
     
-    #[arg(short = 'o', long, value_enum, default_value = "human", help = "Output format")]
-    pub output_format: OutputFormat,
+    #[arg(short = 'o', long, default_value = "human", help = "Output format: human, json, xml, csv, or the name of a registered OutputPlugin")]
+    pub output_format: String,
     
     #[arg(short = 'f', long, help = "Output file path")]
     pub output_file: Option<PathBuf>,
@@ -39,6 +39,24 @@ pub struct Cli {
 
     #[arg(long, help = "Skip host discovery - scan all targets")]
     pub skip_ping: bool,
+
+    #[arg(long, help = "Run continuously, rescanning targets on an interval and reporting deltas")]
+    pub watch: bool,
+
+    #[arg(long, default_value = "300", help = "Seconds between rescans in --watch mode")]
+    pub watch_interval: u64,
+
+    #[arg(long, help = "Route TCP connect scans through a SOCKS5 proxy, e.g. socks5://127.0.0.1:1080")]
+    pub proxy: Option<String>,
+
+    #[arg(long, help = "Send a PROXY protocol v2 header on each TCP connect scan, for targets behind a load balancer that requires one")]
+    pub proxy_protocol: bool,
+
+    #[arg(long, value_enum, default_value = "serial", help = "Port probe order: serial (as given), random (seeded shuffle), or adaptive (front-load historically-open ports)")]
+    pub scan_order: ScanOrder,
+
+    #[arg(long, help = "Seed for --scan-order random, to replay a previous run's probe order")]
+    pub scan_seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -70,14 +88,28 @@ impl std::fmt::Display for ScanType {
     }
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
-pub enum OutputFormat {
-    #[value(name = "human", help = "Human-readable output")]
-    Human,
-    #[value(name = "json", help = "JSON output")]
-    Json,
-    #[value(name = "xml", help = "XML output (Nmap compatible)")]
-    Xml,
-    #[value(name = "csv", help = "CSV output")]
-    Csv,
-}
\ No newline at end of file
+/// Order in which a host's ports are probed.
+#[derive(Debug, Clone, Copy, ValueEnum, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+pub enum ScanOrder {
+    /// Probe in the exact order given - the current, trivially fingerprintable default.
+    #[default]
+    #[value(name = "serial", help = "Probe ports in the order given")]
+    Serial,
+    /// Shuffle with a seeded RNG, recording the seed for reproducibility.
+    #[value(name = "random", help = "Shuffle ports with a seeded RNG")]
+    Random,
+    /// Front-load ports this network class has historically had open.
+    #[value(name = "adaptive", help = "Front-load historically-open ports first")]
+    Adaptive,
+}
+
+impl std::fmt::Display for ScanOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanOrder::Serial => write!(f, "SERIAL"),
+            ScanOrder::Random => write!(f, "RANDOM"),
+            ScanOrder::Adaptive => write!(f, "ADAPTIVE"),
+        }
+    }
+}
+
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::fs;
+use std::sync::Arc;
 use anyhow::Result;
+use tokio::sync::RwLock;
+
+use crate::hooks::HooksConfig;
 
 /// Central configuration for portscan-rs
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +15,30 @@ pub struct Config {
     pub output: OutputConfig,
     pub storage: StorageConfig,
     pub performance: PerformanceConfig,
+    pub plugins: PluginsConfig,
+    pub hooks: HooksConfig,
+}
+
+/// Settings for discovering and loading external (out-of-tree) plugins.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginsConfig {
+    /// Directory scanned for `.so`/`.dll`/`.dylib` plugin libraries.
+    pub plugins_dir: Option<PathBuf>,
+    /// Explicit plugin library paths to load in addition to `plugins_dir`.
+    pub paths: Vec<PathBuf>,
+    /// Per-plugin `enabled`/`priority`/`settings` overrides, keyed by plugin name.
+    /// Re-read on config hot-reload so operators can retune a running instance.
+    pub plugin_configs: std::collections::HashMap<String, crate::plugins::PluginConfig>,
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self {
+            plugins_dir: None,
+            paths: Vec::new(),
+            plugin_configs: std::collections::HashMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,36 +129,172 @@ impl Default for Config {
                 enable_scan_caching: false,
                 cache_ttl_seconds: 300,
             },
+            plugins: PluginsConfig::default(),
+            hooks: HooksConfig::default(),
+        }
+    }
+}
+
+/// On-disk config formats, auto-detected from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => ConfigFormat::Toml,
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Json,
+        }
+    }
+
+    fn parse(self, content: &str) -> Result<Config> {
+        match self {
+            ConfigFormat::Json => Ok(serde_json::from_str(content)?),
+            ConfigFormat::Toml => Ok(toml::from_str(content)?),
+            ConfigFormat::Yaml => Ok(serde_yaml::from_str(content)?),
+        }
+    }
+
+    fn serialize(self, config: &Config) -> Result<String> {
+        match self {
+            ConfigFormat::Json => Ok(serde_json::to_string_pretty(config)?),
+            ConfigFormat::Toml => Ok(toml::to_string_pretty(config)?),
+            ConfigFormat::Yaml => Ok(serde_yaml::to_string(config)?),
         }
     }
 }
 
 impl Config {
-    /// Load configuration from the standard config directory
+    /// Load configuration from the standard config directory, auto-detecting
+    /// JSON/TOML/YAML from whichever `config.*` file is found there.
     pub fn load() -> Result<Self> {
-        let config_path = Self::get_config_path();
-        
+        let config_path = Self::resolve_config_path();
+
         if config_path.exists() {
             let content = fs::read_to_string(&config_path)?;
-            let config: Config = serde_json::from_str(&content)?;
-            Ok(config)
+            ConfigFormat::from_path(&config_path).parse(&content)
         } else {
             let config = Self::default();
             config.save()?;
             Ok(config)
         }
     }
+
+    /// Find the config file actually present on disk, trying
+    /// `config.json`/`config.toml`/`config.yaml`/`config.yml` in that order,
+    /// falling back to the default JSON path if none exist yet.
+    pub fn resolve_config_path() -> PathBuf {
+        let base = Self::get_config_path();
+        let dir = base.parent().map(PathBuf::from).unwrap_or_default();
+
+        for candidate in ["config.json", "config.toml", "config.yaml", "config.yml"] {
+            let path = dir.join(candidate);
+            if path.exists() {
+                return path;
+            }
+        }
+
+        base
+    }
     
-    /// Save configuration to the standard config directory
+    /// Load the config once, then keep it current for the lifetime of a
+    /// long-running process (e.g. `--watch` mode): returns a shared handle
+    /// that a background task hot-swaps in place whenever `config_path`
+    /// changes on disk, so callers holding the `Arc` see the new values on
+    /// their next read without needing a restart. A malformed edit is
+    /// logged and the previous, still-valid config is left in place.
+    pub fn watch(config_path: PathBuf) -> Result<Arc<RwLock<Config>>> {
+        let initial = if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            ConfigFormat::from_path(&config_path).parse(&content)?
+        } else {
+            Self::default()
+        };
+
+        let shared = Arc::new(RwLock::new(initial));
+        let watched = shared.clone();
+
+        tokio::spawn(async move {
+            use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+            let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+            let mut watcher = match RecommendedWatcher::new(
+                move |res: notify::Result<notify::Event>| {
+                    if let Ok(event) = res {
+                        let _ = tx.blocking_send(event);
+                    }
+                },
+                notify::Config::default(),
+            ) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    eprintln!("Warning: failed to create config watcher: {}", e);
+                    return;
+                }
+            };
+
+            if let Err(e) = watcher.watch(&config_path, RecursiveMode::NonRecursive) {
+                eprintln!("Warning: failed to watch {}: {}", config_path.display(), e);
+                return;
+            }
+
+            while let Some(event) = rx.recv().await {
+                if !matches!(
+                    event.kind,
+                    notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+                ) {
+                    continue;
+                }
+
+                match Self::reload_from(&config_path).await {
+                    Ok(new_config) => {
+                        *watched.write().await = new_config;
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "Warning: config reload from {} failed, keeping current config: {}",
+                            config_path.display(),
+                            e
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok(shared)
+    }
+
+    async fn reload_from(config_path: &std::path::Path) -> Result<Config> {
+        let content = tokio::fs::read_to_string(config_path).await?;
+        let config = Self::parse_str(&content, config_path)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse config file content, auto-detecting JSON/TOML/YAML from
+    /// `path`'s extension. Exposed for other consumers (e.g. the plugin
+    /// manager's own config-file watcher) that need to re-read the same
+    /// file this module does.
+    pub fn parse_str(content: &str, path: &std::path::Path) -> Result<Config> {
+        ConfigFormat::from_path(path).parse(content)
+    }
+
+    /// Save configuration to the standard config directory, in whichever
+    /// format is already on disk (or JSON, for a brand new config).
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::get_config_path();
-        
+        let config_path = Self::resolve_config_path();
+
         // Ensure parent directory exists
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)?;
         }
-        
-        let content = serde_json::to_string_pretty(self)?;
+
+        let content = ConfigFormat::from_path(&config_path).serialize(self)?;
         fs::write(&config_path, content)?;
         Ok(())
     }
@@ -245,6 +409,8 @@ impl Config {
                 enable_scan_caching: false,
                 cache_ttl_seconds: 60,
             },
+            plugins: PluginsConfig::default(),
+            hooks: HooksConfig::default(),
         }
     }
 }
@@ -282,4 +448,41 @@ mod tests {
         config_disabled.adaptive.enabled = false;
         assert_eq!(config_disabled.get_effective_timeout(Some(500)), config.scanning.default_timeout);
     }
+
+    #[test]
+    fn test_toml_and_yaml_round_trip() {
+        let config = Config::default();
+
+        let toml_str = ConfigFormat::Toml.serialize(&config).unwrap();
+        let from_toml = ConfigFormat::Toml.parse(&toml_str).unwrap();
+        assert_eq!(config.scanning.default_timeout, from_toml.scanning.default_timeout);
+
+        let yaml_str = ConfigFormat::Yaml.serialize(&config).unwrap();
+        let from_yaml = ConfigFormat::Yaml.parse(&yaml_str).unwrap();
+        assert_eq!(config.adaptive.learning_rate, from_yaml.adaptive.learning_rate);
+    }
+
+    #[test]
+    fn test_config_format_from_path_detects_extension() {
+        assert_eq!(ConfigFormat::from_path(&PathBuf::from("config.toml")), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path(&PathBuf::from("config.yaml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(&PathBuf::from("config.yml")), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path(&PathBuf::from("config.json")), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path(&PathBuf::from("config")), ConfigFormat::Json);
+    }
+
+    #[tokio::test]
+    async fn test_reload_from_rejects_invalid_config() {
+        let dir = std::env::temp_dir().join(format!("mlscan-test-config-{:?}", std::thread::current().id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+
+        let mut config = Config::default();
+        config.scanning.default_timeout = 0; // invalid
+        fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+
+        assert!(Config::reload_from(&path).await.is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,206 @@
+//! Long-running "watch" scan mode plus systemd `sd-notify` integration.
+//!
+//! `run_watch` repeatedly scans the same target/port set on a fixed interval
+//! and reports deltas against the previous run. `SdNotifier` speaks the
+//! `sd-notify` datagram protocol so the process can report readiness,
+//! progress, and liveness to a supervising systemd unit; when `NOTIFY_SOCKET`
+//! isn't set (i.e. not running under systemd) every call is a no-op.
+
+use std::collections::HashSet;
+use std::env;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::net::UnixDatagram;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::cli::ScanType;
+use crate::config::Config;
+use crate::output::OutputWriter;
+use crate::scanner::{PortStatus, Scanner};
+
+/// A thin client for the systemd `sd-notify` protocol over `NOTIFY_SOCKET`.
+/// All methods are no-ops when the process wasn't started under systemd.
+pub struct SdNotifier {
+    socket: Option<UnixDatagram>,
+}
+
+impl SdNotifier {
+    /// Build a notifier from the `NOTIFY_SOCKET` environment variable.
+    ///
+    /// Gated behind the `systemd` crate feature: with it off, this always
+    /// returns a notifier with no socket, so every method below is a no-op
+    /// regardless of the environment - builds that don't want the
+    /// sd-notify dependency at all can disable the feature rather than
+    /// relying on `NOTIFY_SOCKET` simply being unset.
+    pub fn from_env() -> Self {
+        #[cfg(feature = "systemd")]
+        {
+            let socket = env::var("NOTIFY_SOCKET").ok().and_then(|path| {
+                let path = if let Some(stripped) = path.strip_prefix('@') {
+                    // Abstract socket namespace, denoted with a leading '@'.
+                    format!("\0{}", stripped)
+                } else {
+                    path
+                };
+
+                match UnixDatagram::unbound() {
+                    Ok(socket) => match socket.connect(&path) {
+                        Ok(()) => Some(socket),
+                        Err(e) => {
+                            warn!("failed to connect to NOTIFY_SOCKET {}: {}", path, e);
+                            None
+                        }
+                    },
+                    Err(e) => {
+                        warn!("failed to create notify socket: {}", e);
+                        None
+                    }
+                }
+            });
+
+            Self { socket }
+        }
+
+        #[cfg(not(feature = "systemd"))]
+        Self { socket: None }
+    }
+
+    fn send(&self, message: &str) {
+        if let Some(socket) = &self.socket {
+            if let Err(e) = socket.send(message.as_bytes()) {
+                warn!("sd-notify send failed: {}", e);
+            }
+        }
+    }
+
+    /// Notify systemd the service has finished starting up.
+    pub fn ready(&self) {
+        self.send("READY=1");
+    }
+
+    /// Send a human-readable status line shown by `systemctl status`.
+    pub fn status(&self, message: &str) {
+        self.send(&format!("STATUS={}", message));
+    }
+
+    /// Send a watchdog keep-alive. Only meaningful if the unit sets `WatchdogSec=`.
+    pub fn watchdog(&self) {
+        self.send("WATCHDOG=1");
+    }
+
+    /// Notify systemd the service is shutting down.
+    pub fn stopping(&self) {
+        self.send("STOPPING=1");
+    }
+
+    /// The watchdog interval the supervisor expects heartbeats within, if any.
+    #[cfg(feature = "systemd")]
+    pub fn watchdog_interval() -> Option<Duration> {
+        env::var("WATCHDOG_USEC")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_micros)
+    }
+
+    #[cfg(not(feature = "systemd"))]
+    pub fn watchdog_interval() -> Option<Duration> {
+        None
+    }
+}
+
+/// Options controlling the watch loop.
+pub struct WatchConfig {
+    pub interval: Duration,
+}
+
+/// Snapshot of open ports per host from the previous iteration, used to
+/// compute deltas between runs.
+type OpenPortSet = HashSet<(IpAddr, u16)>;
+
+/// Repeatedly scan `target`/`ports` every `config.interval`, reporting newly
+/// opened/closed ports since the last iteration and keeping the systemd
+/// watchdog fed in the background.
+pub async fn run_watch(
+    mut scanner: Scanner,
+    target: String,
+    ports: String,
+    scan_type: ScanType,
+    output_writer: OutputWriter,
+    config: WatchConfig,
+    notifier: SdNotifier,
+    live_config: Option<Arc<RwLock<Config>>>,
+) -> Result<()> {
+    notifier.ready();
+
+    if let Some(interval) = SdNotifier::watchdog_interval() {
+        spawn_watchdog(interval);
+    }
+
+    let mut previous_open: OpenPortSet = HashSet::new();
+    let mut iteration: u64 = 0;
+
+    loop {
+        iteration += 1;
+
+        if let Some(live_config) = &live_config {
+            scanner.apply_config(&*live_config.read().await);
+        }
+
+        notifier.status(&format!("scanning {} (iteration {})", target, iteration));
+
+        let results = scanner.scan(&target, &ports, scan_type).await?;
+
+        let mut current_open: OpenPortSet = HashSet::new();
+        let mut open_count = 0;
+        for host in &results.hosts {
+            for port in &host.ports {
+                if port.status == PortStatus::Open {
+                    open_count += 1;
+                    current_open.insert((host.target_ip, port.port));
+                }
+            }
+        }
+
+        notifier.status(&format!(
+            "scanning {}, {}/{} hosts, {} open ports",
+            target,
+            results.hosts.len(),
+            results.total_hosts,
+            open_count
+        ));
+
+        for newly_open in current_open.difference(&previous_open) {
+            info!("delta: {}:{} is now OPEN", newly_open.0, newly_open.1);
+        }
+        for newly_closed in previous_open.difference(&current_open) {
+            info!("delta: {}:{} is now CLOSED", newly_closed.0, newly_closed.1);
+        }
+
+        output_writer.write(results).await?;
+        previous_open = current_open;
+
+        sleep(config.interval).await;
+    }
+}
+
+/// Spawn a background task pinging `WATCHDOG=1` at half of `interval`, as
+/// systemd recommends so a single missed tick doesn't trip the watchdog.
+/// Shared by `run_watch`'s loop and a single one-shot scan run long enough
+/// under `Type=notify`+`WatchdogSec=` to need heartbeats of its own.
+pub(crate) fn spawn_watchdog(interval: Duration) {
+    // Ping at half the requested interval, as systemd recommends, so a single
+    // missed tick doesn't trip the watchdog.
+    let ping_interval = interval / 2;
+    tokio::spawn(async move {
+        let notifier = SdNotifier::from_env();
+        loop {
+            sleep(ping_interval).await;
+            notifier.watchdog();
+        }
+    });
+}
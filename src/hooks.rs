@@ -0,0 +1,183 @@
+//! External hook-script subsystem: lets users wire shell commands to scan
+//! lifecycle events (see `HookEvent`), similar to how `OutputWriter::write`
+//! is the single emission point for results.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+/// A single point in a scan's lifecycle that a hook can be attached to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HookEvent {
+    ScanStarted,
+    HostUp { host: IpAddr },
+    OpenPortFound { host: IpAddr, port: u16, service: Option<String> },
+    ScanComplete,
+}
+
+impl HookEvent {
+    /// The config key used to look up a command template for this event.
+    fn key(&self) -> &'static str {
+        match self {
+            HookEvent::ScanStarted => "scan_started",
+            HookEvent::HostUp { .. } => "host_up",
+            HookEvent::OpenPortFound { .. } => "open_port_found",
+            HookEvent::ScanComplete => "scan_complete",
+        }
+    }
+
+    /// Environment variables exposed to the hook command for this event.
+    fn env_vars(&self) -> HashMap<String, String> {
+        let mut env = HashMap::new();
+        match self {
+            HookEvent::ScanStarted | HookEvent::ScanComplete => {}
+            HookEvent::HostUp { host } => {
+                env.insert("MLSCAN_HOST".to_string(), host.to_string());
+            }
+            HookEvent::OpenPortFound { host, port, service } => {
+                env.insert("MLSCAN_HOST".to_string(), host.to_string());
+                env.insert("MLSCAN_PORT".to_string(), port.to_string());
+                if let Some(service) = service {
+                    env.insert("MLSCAN_SERVICE".to_string(), service.clone());
+                }
+            }
+        }
+        env
+    }
+}
+
+/// Per-event hook configuration: a shell command template to run and a
+/// timeout after which the child process is killed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookConfig {
+    pub command: String,
+    pub timeout_ms: u64,
+}
+
+/// Maps lifecycle events to the hooks that should run for them.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HooksConfig {
+    pub scan_started: Option<HookConfig>,
+    pub host_up: Option<HookConfig>,
+    pub open_port_found: Option<HookConfig>,
+    pub scan_complete: Option<HookConfig>,
+}
+
+impl HooksConfig {
+    fn lookup(&self, key: &str) -> Option<&HookConfig> {
+        match key {
+            "scan_started" => self.scan_started.as_ref(),
+            "host_up" => self.host_up.as_ref(),
+            "open_port_found" => self.open_port_found.as_ref(),
+            "scan_complete" => self.scan_complete.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Fires hook commands for scan lifecycle events without blocking the scan.
+#[derive(Clone, Default)]
+pub struct HookRunner {
+    config: HooksConfig,
+}
+
+impl HookRunner {
+    pub fn new(config: HooksConfig) -> Self {
+        Self { config }
+    }
+
+    /// Fire a hook for `event`, if one is configured. Spawns the command
+    /// asynchronously and returns immediately - a slow hook never stalls the scan.
+    pub fn fire(&self, event: HookEvent) {
+        let Some(hook) = self.config.lookup(event.key()).cloned() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let env = event.env_vars();
+            if let Err(e) = run_hook(&hook, env).await {
+                warn!("hook for {} failed: {}", event.key(), e);
+            }
+        });
+    }
+}
+
+async fn run_hook(hook: &HookConfig, env: HashMap<String, String>) -> anyhow::Result<()> {
+    let mut command = build_command(&hook.command);
+    command.envs(&env);
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+
+    let mut child = command.spawn()?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    if let Some(stdout) = stdout {
+        tokio::spawn(stream_to_log(stdout, false));
+    }
+    if let Some(stderr) = stderr {
+        tokio::spawn(stream_to_log(stderr, true));
+    }
+
+    match timeout(Duration::from_millis(hook.timeout_ms), child.wait()).await {
+        Ok(status) => {
+            status?;
+            Ok(())
+        }
+        Err(_) => {
+            let _ = child.kill().await;
+            anyhow::bail!("hook timed out after {}ms and was killed", hook.timeout_ms)
+        }
+    }
+}
+
+async fn stream_to_log<R: tokio::io::AsyncRead + Unpin>(reader: R, is_stderr: bool) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if is_stderr {
+            warn!("hook stderr: {}", line);
+        } else {
+            info!("hook stdout: {}", line);
+        }
+    }
+}
+
+fn build_command(template: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(template);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_event_key() {
+        assert_eq!(HookEvent::ScanStarted.key(), "scan_started");
+        assert_eq!(
+            HookEvent::OpenPortFound { host: "127.0.0.1".parse().unwrap(), port: 80, service: None }.key(),
+            "open_port_found"
+        );
+    }
+
+    #[test]
+    fn test_env_vars_for_open_port() {
+        let event = HookEvent::OpenPortFound {
+            host: "127.0.0.1".parse().unwrap(),
+            port: 443,
+            service: Some("HTTPS".to_string()),
+        };
+        let env = event.env_vars();
+        assert_eq!(env.get("MLSCAN_HOST").unwrap(), "127.0.0.1");
+        assert_eq!(env.get("MLSCAN_PORT").unwrap(), "443");
+        assert_eq!(env.get("MLSCAN_SERVICE").unwrap(), "HTTPS");
+    }
+}
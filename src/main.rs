@@ -6,33 +6,68 @@ mod network;
 mod adaptive;
 mod config;
 mod plugins;
+mod hooks;
+mod daemon;
+mod proxy;
+mod proxy_protocol;
+mod storage;
 
 use anyhow::Result;
 use clap::Parser;
 use colored::*;
 use std::io::{self, Write};
+use std::sync::Arc;
 use tracing_subscriber;
 
 use crate::cli::Cli;
+use crate::config::{Config, StorageBackend};
+use crate::daemon::{run_watch, spawn_watchdog, SdNotifier, WatchConfig};
+use crate::hooks::HookRunner;
+use crate::plugins::manager::ThreadSafePluginManager;
 use crate::scanner::Scanner;
 use crate::output::OutputWriter;
+use crate::storage::SqliteStorage;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
-    
+
     tracing_subscriber::fmt::init();
-    
+
     // No legal BS, just pure scanning action! 🔥
-    
+
+    let config = Config::load()?;
+    config.validate()?;
+
+    let plugin_manager = Arc::new(ThreadSafePluginManager::new());
+    plugin_manager.load_from_config(&config).await?;
+    plugin_manager.watch_config(Config::resolve_config_path());
+
+    let notifier = SdNotifier::from_env();
+
+    let proxy = cli.proxy.as_deref().map(crate::proxy::ProxyConfig::parse).transpose()?;
+
+    let storage = match config.storage.backend {
+        StorageBackend::Sqlite => Some(Arc::new(SqliteStorage::open(&config).await?)),
+        StorageBackend::Json | StorageBackend::Memory => None,
+    };
+
     let mut scanner = Scanner::new(
         cli.rate_limit,
         cli.timeout,
         cli.parallel_hosts,
-    );
-    
-    let output_writer = OutputWriter::new(cli.output_format, cli.output_file)?;
-    
+    )
+        .with_hooks(HookRunner::new(config.hooks.clone()))
+        .with_proxy(proxy)
+        .with_proxy_protocol(cli.proxy_protocol)
+        .with_adaptive_config(&config.adaptive)
+        .with_scan_order(cli.scan_order, cli.scan_seed)
+        .with_plugin_manager(plugin_manager.clone())
+        .with_storage(storage.clone());
+
+    let output_writer = OutputWriter::new(cli.output_format, cli.output_file)?
+        .with_plugin_manager(plugin_manager.clone());
+
     // Check if target is provided
     if cli.target.is_empty() {
         eprintln!("{}", "Error: No target specified. Use -t to specify a target.".red());
@@ -40,16 +75,67 @@ async fn main() -> Result<()> {
         eprintln!("Run 'mlscan --help' for more information.");
         std::process::exit(1);
     }
-    
+
     let target_spec = cli.target.join(",");
     let ports_spec = cli.ports.join(",");
+
+    if cli.watch {
+        let watch_config = WatchConfig {
+            interval: std::time::Duration::from_secs(cli.watch_interval),
+        };
+        // Hot-reload the full config (not just plugin settings) for the
+        // duration of a long-running watch, so timeout/rate-limit/hook
+        // edits apply without a restart.
+        let live_config = Some(Config::watch(Config::resolve_config_path())?);
+        let result = run_watch(
+            scanner,
+            target_spec,
+            ports_spec,
+            cli.scan_type,
+            output_writer,
+            watch_config,
+            notifier,
+            live_config,
+        ).await;
+        return result;
+    }
+
+    // Config is loaded/validated and the scan engine is fully built - this is
+    // the point a supervising systemd unit should consider us up.
+    notifier.ready();
+
+    if let Some(interval) = SdNotifier::watchdog_interval() {
+        spawn_watchdog(interval);
+    }
+
+    notifier.status(&format!("scanning {}", target_spec));
+
     let results = scanner.scan(
         &target_spec,
         &ports_spec,
         cli.scan_type,
     ).await?;
-    
-    output_writer.write(results)?;
-    
+
+    let open_count = results.hosts.iter()
+        .flat_map(|host| &host.ports)
+        .filter(|port| port.status == crate::scanner::PortStatus::Open)
+        .count();
+    notifier.status(&format!(
+        "scan complete: {}/{} hosts, {} open ports",
+        results.hosts.len(),
+        results.total_hosts,
+        open_count
+    ));
+
+    output_writer.write(results).await?;
+
+    if let Some(storage) = &storage {
+        storage.purge_expired(config.adaptive.data_retention_days).await?;
+        storage.vacuum_if_needed().await?;
+    }
+
+    notifier.stopping();
+    plugin_manager.shutdown_all().await?;
+
     Ok(())
 }
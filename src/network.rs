@@ -0,0 +1,164 @@
+//! Expands a `--target` spec into concrete `IpAddr`s to scan.
+//!
+//! A spec is a comma-separated list of tokens, each of which is a literal IP,
+//! an IP range (`10.0.0.1-10.0.0.20`), a CIDR block (`10.0.0.0/28`), or a
+//! hostname/DNS name resolved via `ToSocketAddrs`. Hostnames can resolve to
+//! more than one address (e.g. a round-robin A record), in which case every
+//! resolved address is scanned under that same hostname. The expanded set is
+//! deduped by IP so overlapping tokens (a CIDR block that also contains a
+//! literal IP given separately, say) don't scan the same host twice.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs};
+
+use anyhow::{anyhow, Result};
+use tracing::warn;
+
+/// One address to scan, paired with the spec token it came from so output
+/// can show the original hostname alongside the IP it resolved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTarget {
+    pub original: String,
+    pub ip: IpAddr,
+}
+
+/// Parse a comma-separated target spec into the deduped set of addresses to
+/// scan. A token that fails to resolve (typically a bad hostname) is warned
+/// about and skipped rather than failing the whole spec.
+pub fn parse_targets(spec: &str) -> Result<Vec<ResolvedTarget>> {
+    let mut seen_ips = std::collections::HashSet::new();
+    let mut targets = Vec::new();
+
+    for token in spec.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        let expanded = match expand_token(token) {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                warn!("skipping target '{}': {}", token, e);
+                continue;
+            }
+        };
+
+        for target in expanded {
+            if seen_ips.insert(target.ip) {
+                targets.push(target);
+            }
+        }
+    }
+
+    if targets.is_empty() {
+        return Err(anyhow!("no valid targets in spec '{}'", spec));
+    }
+
+    Ok(targets)
+}
+
+/// Expand a single spec token (IP, range, CIDR, or hostname) into one or
+/// more resolved targets.
+fn expand_token(token: &str) -> Result<Vec<ResolvedTarget>> {
+    if let Ok(ip) = token.parse::<IpAddr>() {
+        return Ok(vec![ResolvedTarget { original: token.to_string(), ip }]);
+    }
+
+    if let Some((start, end)) = token.split_once('-') {
+        if let (Ok(start), Ok(end)) = (start.parse::<IpAddr>(), end.parse::<IpAddr>()) {
+            return expand_range(start, end);
+        }
+    }
+
+    if let Some((network, prefix_len)) = token.split_once('/') {
+        if let Ok(network) = network.parse::<IpAddr>() {
+            let prefix_len: u32 = prefix_len
+                .parse()
+                .map_err(|_| anyhow!("invalid CIDR prefix length '{}'", prefix_len))?;
+            return expand_cidr(network, prefix_len);
+        }
+    }
+
+    resolve_hostname(token)
+}
+
+/// Expand an inclusive IP range (`start-end`) into every address in between.
+/// Both ends must be the same address family.
+fn expand_range(start: IpAddr, end: IpAddr) -> Result<Vec<ResolvedTarget>> {
+    match (start, end) {
+        (IpAddr::V4(start), IpAddr::V4(end)) => {
+            let (start, end) = (u32::from(start), u32::from(end));
+            if start > end {
+                return Err(anyhow!("range start {} is after end {}", Ipv4Addr::from(start), Ipv4Addr::from(end)));
+            }
+            Ok((start..=end)
+                .map(|addr| {
+                    let ip = IpAddr::V4(Ipv4Addr::from(addr));
+                    ResolvedTarget { original: ip.to_string(), ip }
+                })
+                .collect())
+        }
+        (IpAddr::V6(start), IpAddr::V6(end)) => {
+            let (start, end) = (u128::from(start), u128::from(end));
+            if start > end {
+                return Err(anyhow!("range start {} is after end {}", Ipv6Addr::from(start), Ipv6Addr::from(end)));
+            }
+            Ok((start..=end)
+                .map(|addr| {
+                    let ip = IpAddr::V6(Ipv6Addr::from(addr));
+                    ResolvedTarget { original: ip.to_string(), ip }
+                })
+                .collect())
+        }
+        _ => Err(anyhow!("range endpoints must be the same address family")),
+    }
+}
+
+/// Expand a CIDR block (`network/prefix_len`) into every address it covers.
+fn expand_cidr(network: IpAddr, prefix_len: u32) -> Result<Vec<ResolvedTarget>> {
+    match network {
+        IpAddr::V4(network) => {
+            if prefix_len > 32 {
+                return Err(anyhow!("IPv4 CIDR prefix length {} is out of range", prefix_len));
+            }
+            let base = u32::from(network) & (!0u32).checked_shl(32 - prefix_len).unwrap_or(0);
+            let host_bits = 32 - prefix_len;
+            if host_bits > 20 {
+                return Err(anyhow!("IPv4 CIDR /{} is too large to expand", prefix_len));
+            }
+            let count = 1u64 << host_bits;
+            Ok((0..count)
+                .map(|offset| {
+                    let ip = IpAddr::V4(Ipv4Addr::from(base + offset as u32));
+                    ResolvedTarget { original: ip.to_string(), ip }
+                })
+                .collect())
+        }
+        IpAddr::V6(network) => {
+            if prefix_len > 128 {
+                return Err(anyhow!("IPv6 CIDR prefix length {} is out of range", prefix_len));
+            }
+            let base = u128::from(network) & (!0u128).checked_shl(128 - prefix_len).unwrap_or(0);
+            let host_bits = 128 - prefix_len;
+            if host_bits > 20 {
+                return Err(anyhow!("IPv6 CIDR /{} is too large to expand", prefix_len));
+            }
+            let count = 1u128 << host_bits;
+            Ok((0..count)
+                .map(|offset| {
+                    let ip = IpAddr::V6(Ipv6Addr::from(base + offset));
+                    ResolvedTarget { original: ip.to_string(), ip }
+                })
+                .collect())
+        }
+    }
+}
+
+/// Resolve a hostname to every address it maps to, via the system resolver.
+fn resolve_hostname(hostname: &str) -> Result<Vec<ResolvedTarget>> {
+    let addrs: Vec<ResolvedTarget> = format!("{}:0", hostname)
+        .to_socket_addrs()
+        .map_err(|e| anyhow!("failed to resolve '{}': {}", hostname, e))?
+        .map(|addr| ResolvedTarget { original: hostname.to_string(), ip: addr.ip() })
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(anyhow!("'{}' did not resolve to any address", hostname));
+    }
+
+    Ok(addrs)
+}
@@ -1,30 +1,54 @@
 use std::fs::File;
 use std::io::{self, Write, BufWriter};
 use std::path::PathBuf;
+use std::sync::Arc;
 use anyhow::Result;
 use colored::*;
 
-use crate::cli::OutputFormat;
+use crate::plugins::manager::ThreadSafePluginManager;
 use crate::scanner::{MultiHostScanResult, ScanResult, PortStatus};
 
+/// Dispatches a finished scan to its output format: one of the built-in
+/// human/json/xml/csv emitters, or - for any other format name - whatever
+/// `OutputPlugin` is registered under that name in the plugin manager. The
+/// built-ins are themselves registered as plugins (see `plugins::builtin`),
+/// so external formats are first-class equals rather than a bolt-on.
 pub struct OutputWriter {
-    format: OutputFormat,
+    format: String,
     file: Option<PathBuf>,
+    plugin_manager: Option<Arc<ThreadSafePluginManager>>,
 }
 
 impl OutputWriter {
-    pub fn new(format: OutputFormat, file: Option<PathBuf>) -> Result<Self> {
-        Ok(Self { format, file })
+    pub fn new(format: String, file: Option<PathBuf>) -> Result<Self> {
+        Ok(Self { format, file, plugin_manager: None })
     }
-    
-    pub fn write(&self, result: MultiHostScanResult) -> Result<()> {
-        let output = match self.format {
-            OutputFormat::Human => self.format_human(result)?,
-            OutputFormat::Json => self.format_json(result)?,
-            OutputFormat::Xml => self.format_xml(result)?,
-            OutputFormat::Csv => self.format_csv(result)?,
+
+    /// Attach a plugin manager so formats beyond the built-in four can be
+    /// resolved to a registered `OutputPlugin`.
+    pub fn with_plugin_manager(mut self, plugin_manager: Arc<ThreadSafePluginManager>) -> Self {
+        self.plugin_manager = Some(plugin_manager);
+        self
+    }
+
+    pub async fn write(&self, result: MultiHostScanResult) -> Result<()> {
+        let output = match self.format.as_str() {
+            "human" => format_human(&result)?,
+            "json" => format_json(&result)?,
+            "xml" => format_xml(&result)?,
+            "csv" => format_csv(&result)?,
+            name => {
+                let plugin_manager = self.plugin_manager.as_ref().ok_or_else(|| {
+                    anyhow::anyhow!("unknown output format '{}' (no plugin manager configured)", name)
+                })?;
+                let plugin = plugin_manager
+                    .get_output_plugin(name)
+                    .await
+                    .ok_or_else(|| anyhow::anyhow!("no output plugin registered for format '{}'", name))?;
+                plugin.format_results(&result).await?
+            }
         };
-        
+
         match &self.file {
             Some(path) => {
                 let file = File::create(path)?;
@@ -37,11 +61,14 @@ impl OutputWriter {
                 io::stdout().flush()?;
             }
         }
-        
+
         Ok(())
     }
-    
-    fn format_human(&self, result: MultiHostScanResult) -> Result<String> {
+}
+
+/// Human-readable cyberpunk-themed report. Shared by `OutputWriter`'s
+/// built-in dispatch and `builtin::HumanOutputPlugin`.
+pub(crate) fn format_human(result: &MultiHostScanResult) -> Result<String> {
         let mut output = String::new();
         
         // Cyberpunk ASCII banner with proper alignment
@@ -99,11 +126,18 @@ impl OutputWriter {
             "⟦SCOPE⟧".truecolor(191, 64, 191).bold(),
             result.total_hosts.to_string().truecolor(255, 255, 255),
             "hosts scanned".truecolor(128, 128, 128)));
-        output.push_str(&format!("{} {} {}\n\n", 
+        output.push_str(&format!("{} {} {}\n",
             "⟦DEPTH⟧".truecolor(191, 64, 191).bold(),
             result.total_ports.to_string().truecolor(255, 255, 255),
             "ports per target".truecolor(128, 128, 128)));
-        
+        if let Some(seed) = result.scan_order_seed {
+            output.push_str(&format!("{} {} {}\n",
+                "⟦ORDER SEED⟧".truecolor(191, 64, 191).bold(),
+                seed.to_string().truecolor(255, 255, 255),
+                "(--scan-order random --scan-seed <this> to replay)".truecolor(128, 128, 128)));
+        }
+        output.push('\n');
+
         let mut hosts_with_open_ports = 0;
         let mut total_open_ports = 0;
         
@@ -153,7 +187,7 @@ impl OutputWriter {
                     "━━━━━━━".truecolor(64, 64, 64)));
                 
                 for port in &open_ports {
-                    let service = get_service_name(port.port);
+                    let service = get_service_name(port.port, result.scan_type);
                     output.push_str(&format!("│ {:<9} {:<13} {:<54} │\n",
                         port.port.to_string().truecolor(255, 255, 255).bold(),
                         "●OPEN".truecolor(0, 255, 65).bold(),
@@ -169,7 +203,7 @@ impl OutputWriter {
                         " ".repeat(filtered_padding)));
                         
                     for port in filtered_ports.iter().take(5) {
-                        let service = get_service_name(port.port);
+                        let service = get_service_name(port.port, result.scan_type);
                         output.push_str(&format!("│ {:<9} {:<13} {:<54} │\n",
                             port.port.to_string().truecolor(255, 255, 255),
                             "◐FLTRD".truecolor(255, 140, 0),
@@ -240,17 +274,17 @@ impl OutputWriter {
                 "╚═══════════════════════════════════════════════════════════════════════════════╝".truecolor(0, 255, 65)));
         }
         
-        output.push_str(&format!("\n{}\n", 
+        output.push_str(&format!("\n{}\n",
             "▓▒░ SCAN OPERATION COMPLETE ░▒▓".truecolor(191, 64, 191).bold()));
-        
+
         Ok(output)
-    }
-    
-    fn format_json(&self, result: MultiHostScanResult) -> Result<String> {
-        Ok(serde_json::to_string_pretty(&result)?)
-    }
-    
-    fn format_xml(&self, result: MultiHostScanResult) -> Result<String> {
+}
+
+pub(crate) fn format_json(result: &MultiHostScanResult) -> Result<String> {
+    Ok(serde_json::to_string_pretty(result)?)
+}
+
+pub(crate) fn format_xml(result: &MultiHostScanResult) -> Result<String> {
         let mut xml = String::new();
         xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
         xml.push_str("<nmaprun>\n");
@@ -278,9 +312,9 @@ impl OutputWriter {
         
         xml.push_str("</nmaprun>\n");
         Ok(xml)
-    }
-    
-    fn format_csv(&self, result: MultiHostScanResult) -> Result<String> {
+}
+
+pub(crate) fn format_csv(result: &MultiHostScanResult) -> Result<String> {
         let mut csv = String::new();
         csv.push_str("target,target_ip,port,status,scan_type\n");
         
@@ -298,10 +332,19 @@ impl OutputWriter {
         }
         
         Ok(csv)
-    }
 }
 
-fn get_service_name(port: u16) -> &'static str {
+fn get_service_name(port: u16, scan_type: crate::cli::ScanType) -> &'static str {
+    // UDP 443 is increasingly QUIC/HTTP3 rather than plain HTTPS, so the
+    // protocol has to disambiguate the label, not just the port number.
+    if scan_type == crate::cli::ScanType::Udp {
+        return match port {
+            53 => "DNS",
+            443 => "HTTP/3 (QUIC)",
+            _ => "UNKNOWN-UDP",
+        };
+    }
+
     match port {
         21 => "FTP",
         22 => "SSH",
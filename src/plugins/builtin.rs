@@ -1,6 +1,7 @@
 use std::net::IpAddr;
 use anyhow::Result;
 use async_trait::async_trait;
+use rand::RngCore;
 
 use super::{ScannerPlugin, OutputPlugin, ServiceDetectionPlugin, PluginConfig, ServiceInfo};
 use crate::scanner::{PortStatus, MultiHostScanResult};
@@ -45,7 +46,7 @@ impl ScannerPlugin for TcpConnectPlugin {
     
     async fn scan_port(&self, target: IpAddr, port: u16, timeout_ms: u64) -> Result<PortStatus> {
         // Use the existing TCP connect scan implementation
-        Ok(crate::scanner::tcp::connect_scan(target, port, timeout_ms).await)
+        Ok(crate::scanner::tcp::connect_scan(target, port, timeout_ms, None, false).await)
     }
 }
 
@@ -81,36 +82,7 @@ impl OutputPlugin for HumanOutputPlugin {
     }
     
     async fn format_results(&self, results: &MultiHostScanResult) -> Result<String> {
-        // Use the existing human output format
-        let mut output = String::new();
-        
-        output.push_str(&format!("Scan Results for: {}\n", results.target_spec));
-        output.push_str(&format!("Scan Type: {:?}\n", results.scan_type));
-        output.push_str(&format!("Start Time: {}\n", results.start_time));
-        output.push_str(&format!("End Time: {}\n", results.end_time));
-        output.push_str(&format!("Total Hosts: {}\n", results.total_hosts));
-        output.push_str(&format!("Total Ports: {}\n\n", results.total_ports));
-        
-        for host_result in &results.hosts {
-            output.push_str(&format!("Host: {}\n", host_result.target));
-            
-            let open_ports: Vec<_> = host_result.ports
-                .iter()
-                .filter(|p| matches!(p.status, PortStatus::Open))
-                .collect();
-            
-            if open_ports.is_empty() {
-                output.push_str("  No open ports found\n\n");
-            } else {
-                output.push_str("  Open Ports:\n");
-                for port_result in open_ports {
-                    output.push_str(&format!("    {} - {}\n", port_result.port, "Open"));
-                }
-                output.push('\n');
-            }
-        }
-        
-        Ok(output)
+        crate::output::format_human(results)
     }
 }
 
@@ -146,7 +118,222 @@ impl OutputPlugin for JsonOutputPlugin {
     }
     
     async fn format_results(&self, results: &MultiHostScanResult) -> Result<String> {
-        serde_json::to_string_pretty(results).map_err(|e| anyhow::anyhow!(e))
+        crate::output::format_json(results)
+    }
+}
+
+/// Built-in XML (Nmap-compatible) output plugin
+pub struct XmlOutputPlugin {
+    name: String,
+}
+
+impl XmlOutputPlugin {
+    pub fn new() -> Self {
+        Self {
+            name: "xml_output".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl OutputPlugin for XmlOutputPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn file_extension(&self) -> &str {
+        "xml"
+    }
+
+    fn content_type(&self) -> &str {
+        "application/xml"
+    }
+
+    async fn initialize(&mut self, _config: PluginConfig) -> Result<()> {
+        Ok(())
+    }
+
+    async fn format_results(&self, results: &MultiHostScanResult) -> Result<String> {
+        crate::output::format_xml(results)
+    }
+}
+
+/// Built-in CSV output plugin
+pub struct CsvOutputPlugin {
+    name: String,
+}
+
+impl CsvOutputPlugin {
+    pub fn new() -> Self {
+        Self {
+            name: "csv_output".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl OutputPlugin for CsvOutputPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn file_extension(&self) -> &str {
+        "csv"
+    }
+
+    fn content_type(&self) -> &str {
+        "text/csv"
+    }
+
+    async fn initialize(&mut self, _config: PluginConfig) -> Result<()> {
+        Ok(())
+    }
+
+    async fn format_results(&self, results: &MultiHostScanResult) -> Result<String> {
+        crate::output::format_csv(results)
+    }
+}
+
+/// Built-in WebSocket output plugin. Rather than handing back one giant
+/// blob like the other output plugins, this streams the results out as a
+/// sequence of JSON messages (one per host, then a final `"done"` control
+/// message) over a WebSocket connection, so a listening dashboard sees hosts
+/// arrive incrementally instead of waiting for the whole scan to format.
+pub struct WebSocketOutputPlugin {
+    name: String,
+    url: Option<String>,
+}
+
+impl WebSocketOutputPlugin {
+    pub fn new() -> Self {
+        Self {
+            name: "websocket_output".to_string(),
+            url: None,
+        }
+    }
+}
+
+#[async_trait]
+impl OutputPlugin for WebSocketOutputPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn file_extension(&self) -> &str {
+        "jsonl"
+    }
+
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+
+    async fn initialize(&mut self, config: PluginConfig) -> Result<()> {
+        self.url = config
+            .settings
+            .get("url")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        Ok(())
+    }
+
+    async fn format_results(&self, results: &MultiHostScanResult) -> Result<String> {
+        use futures_util::SinkExt;
+        use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+        let url = self.url.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("websocket_output requires a 'url' setting (e.g. ws://127.0.0.1:9001)")
+        })?;
+
+        let (mut ws_stream, _) = connect_async(url).await?;
+
+        for host in &results.hosts {
+            let message = serde_json::to_string(host)?;
+            ws_stream.send(Message::Text(message)).await?;
+        }
+        ws_stream.send(Message::Text("\"done\"".to_string())).await?;
+        ws_stream.close(None).await?;
+
+        Ok(format!("Streamed {} host(s) to {}", results.hosts.len(), url))
+    }
+}
+
+/// Output plugin that publishes each host's result to a NATS subject instead
+/// of returning a file string, so a fleet of scanners can feed a shared
+/// collector. Connects once in `initialize`; `format_results` just publishes.
+pub struct NatsOutputPlugin {
+    name: String,
+    subject_prefix: String,
+    client: Option<async_nats::Client>,
+}
+
+impl NatsOutputPlugin {
+    pub fn new() -> Self {
+        Self {
+            name: "nats_output".to_string(),
+            subject_prefix: "mlscan.results".to_string(),
+            client: None,
+        }
+    }
+}
+
+#[async_trait]
+impl OutputPlugin for NatsOutputPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn file_extension(&self) -> &str {
+        "jsonl"
+    }
+
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+
+    async fn initialize(&mut self, config: PluginConfig) -> Result<()> {
+        if let Some(prefix) = config.settings.get("subject_prefix").and_then(|v| v.as_str()) {
+            self.subject_prefix = prefix.to_string();
+        }
+
+        let Some(url) = config.settings.get("url").and_then(|v| v.as_str()) else {
+            // Not configured (e.g. the default built-in load with no
+            // settings); format_results reports the missing connection.
+            return Ok(());
+        };
+
+        let mut options = async_nats::ConnectOptions::new();
+        let username = config.settings.get("username").and_then(|v| v.as_str());
+        let password = config.settings.get("password").and_then(|v| v.as_str());
+        let token = config.settings.get("token").and_then(|v| v.as_str());
+
+        if let (Some(username), Some(password)) = (username, password) {
+            options = options.user_and_password(username.to_string(), password.to_string());
+        } else if let Some(token) = token {
+            options = options.token(token.to_string());
+        }
+
+        self.client = Some(options.connect(url).await?);
+        Ok(())
+    }
+
+    async fn format_results(&self, results: &MultiHostScanResult) -> Result<String> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("nats_output requires a 'url' setting (e.g. nats://127.0.0.1:4222)")
+        })?;
+
+        let mut published = 0usize;
+        for host in &results.hosts {
+            let subject = format!("{}.{}", self.subject_prefix, host.target_ip);
+            let payload = serde_json::to_vec(host)?;
+            client.publish(subject, payload.into()).await?;
+            published += 1;
+        }
+        client.flush().await?;
+
+        Ok(format!(
+            "Published {} host result(s) to NATS under '{}.*'",
+            published, self.subject_prefix
+        ))
     }
 }
 
@@ -288,10 +475,250 @@ impl ServiceDetectionPlugin for SshServicePlugin {
     }
 }
 
+/// QUIC version used for the Initial packet probe (the only version widely
+/// deployed today).
+const QUIC_VERSION_1: u32 = 0x0000_0001;
+
+/// Packet-type bits (bits 4-5 of the first byte) for a version-1 long
+/// header. 0-RTT (0x01) and Handshake (0x02) are never valid replies to a
+/// client Initial, so they aren't accepted below.
+const QUIC_LONG_HEADER_TYPE_INITIAL: u8 = 0x00;
+const QUIC_LONG_HEADER_TYPE_RETRY: u8 = 0x03;
+
+/// RFC 9000 section 14.1: a UDP datagram carrying a client Initial packet
+/// must be padded to at least this many bytes, or a spec-compliant server
+/// discards it outright instead of replying.
+const QUIC_MIN_INITIAL_DATAGRAM_LEN: usize = 1200;
+
+/// Build a minimal QUIC Initial packet: a long header advertising version 1,
+/// a random 8-byte Destination Connection ID, and a CRYPTO frame carrying a
+/// (deliberately minimal) TLS ClientHello that advertises `h3` via ALPN.
+/// Returns the padded probe datagram alongside the Destination Connection ID
+/// it used, so the caller can confirm a reply actually echoes it back.
+fn build_quic_initial_probe() -> (Vec<u8>, [u8; 8]) {
+    let mut dcid = [0u8; 8];
+    rand::thread_rng().fill_bytes(&mut dcid);
+
+    // ALPN extension: extension type 0x0010, advertising the single protocol "h3".
+    let alpn_list = [0x02u8, b'h', b'3'];
+    let alpn_ext = {
+        let mut ext = Vec::new();
+        ext.extend_from_slice(&0x0010u16.to_be_bytes());
+        ext.extend_from_slice(&((alpn_list.len() + 2) as u16).to_be_bytes());
+        ext.extend_from_slice(&(alpn_list.len() as u16).to_be_bytes());
+        ext.extend_from_slice(&alpn_list);
+        ext
+    };
+
+    // A deliberately bare-bones ClientHello body - real QUIC stacks validate
+    // far more, but this is enough to identify us as "a QUIC Initial" to any
+    // server willing to answer with a Version Negotiation or Retry packet.
+    let mut client_hello_body = Vec::new();
+    client_hello_body.extend_from_slice(&0x0303u16.to_be_bytes()); // legacy_version: TLS 1.2
+    client_hello_body.extend_from_slice(&[0u8; 32]); // random
+    client_hello_body.push(0); // session_id length
+    client_hello_body.extend_from_slice(&0x0002u16.to_be_bytes()); // cipher suites length
+    client_hello_body.extend_from_slice(&0x1301u16.to_be_bytes()); // TLS_AES_128_GCM_SHA256
+    client_hello_body.push(1); // compression methods length
+    client_hello_body.push(0); // null compression
+    client_hello_body.extend_from_slice(&(alpn_ext.len() as u16).to_be_bytes());
+    client_hello_body.extend_from_slice(&alpn_ext);
+
+    let mut crypto_frame = Vec::new();
+    crypto_frame.push(0x06); // CRYPTO frame type
+    crypto_frame.push(0x00); // offset = 0
+    crypto_frame.push(client_hello_body.len() as u8);
+    crypto_frame.extend_from_slice(&client_hello_body);
+
+    let mut packet = Vec::new();
+    packet.push(0xC3); // long header, fixed bit set, Initial packet type
+    packet.extend_from_slice(&QUIC_VERSION_1.to_be_bytes());
+    packet.push(dcid.len() as u8);
+    packet.extend_from_slice(&dcid);
+    packet.push(0); // empty Source Connection ID
+    packet.push(0); // empty token
+    packet.extend_from_slice(&(crypto_frame.len() as u16).to_be_bytes());
+    packet.extend_from_slice(&crypto_frame);
+
+    // Pad the datagram out to the RFC-mandated minimum with 0x00 bytes. They
+    // follow the CRYPTO frame but lie outside the Length field written above,
+    // which covers the CRYPTO frame only.
+    if packet.len() < QUIC_MIN_INITIAL_DATAGRAM_LEN {
+        packet.resize(QUIC_MIN_INITIAL_DATAGRAM_LEN, 0);
+    }
+
+    (packet, dcid)
+}
+
+/// A UDP response counts as "this is QUIC" only if it's a long-header packet
+/// that's plausibly actually replying to our Initial: either a Version
+/// Negotiation packet that echoes `sent_dcid` back as its Source Connection
+/// ID (RFC 9000 section 6), or an Initial/Retry packet using our version
+/// that still addresses us via the Destination Connection ID we originally
+/// chose (we sent a zero-length Source Connection ID, so there's nothing
+/// else of ours for it to echo). Anything else - a high bit set by
+/// coincidence, a reply to some other connection, a Handshake/0-RTT packet
+/// that could never legitimately answer a client Initial - is rejected.
+fn looks_like_quic_response(buf: &[u8], sent_dcid: &[u8; 8]) -> bool {
+    // 1-byte header + 4-byte version + 1-byte DCID length, at minimum.
+    if buf.len() < 6 {
+        return false;
+    }
+    let first_byte = buf[0];
+    if first_byte & 0x80 == 0 {
+        return false;
+    }
+
+    let version = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]);
+
+    let dcid_len = buf[5] as usize;
+    if buf.len() < 6 + dcid_len + 1 {
+        return false;
+    }
+    let dcid = &buf[6..6 + dcid_len];
+
+    let scid_len_offset = 6 + dcid_len;
+    let scid_len = buf[scid_len_offset] as usize;
+    if buf.len() < scid_len_offset + 1 + scid_len {
+        return false;
+    }
+    let scid = &buf[scid_len_offset + 1..scid_len_offset + 1 + scid_len];
+
+    if version == 0 {
+        return scid == sent_dcid;
+    }
+    if version != QUIC_VERSION_1 {
+        return false;
+    }
+
+    let packet_type = (first_byte >> 4) & 0x03;
+    if packet_type != QUIC_LONG_HEADER_TYPE_INITIAL && packet_type != QUIC_LONG_HEADER_TYPE_RETRY {
+        return false;
+    }
+
+    dcid == sent_dcid
+}
+
+/// Built-in QUIC/HTTP3 scanner plugin: probes UDP ports for a QUIC responder.
+pub struct QuicProbePlugin {
+    name: String,
+    version: String,
+}
+
+impl QuicProbePlugin {
+    pub fn new() -> Self {
+        Self {
+            name: "quic_probe".to_string(),
+            version: "1.0.0".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ScannerPlugin for QuicProbePlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn description(&self) -> &str {
+        "Probes UDP ports for QUIC/HTTP3 responders using a minimal QUIC Initial packet"
+    }
+
+    fn supported_scan_types(&self) -> Vec<ScanType> {
+        vec![ScanType::Udp]
+    }
+
+    async fn initialize(&mut self, _config: PluginConfig) -> Result<()> {
+        Ok(())
+    }
+
+    async fn scan_port(&self, target: IpAddr, port: u16, timeout_ms: u64) -> Result<PortStatus> {
+        use tokio::net::UdpSocket;
+        use tokio::time::{timeout, Duration};
+
+        let bind_addr = match target {
+            IpAddr::V4(_) => "0.0.0.0:0",
+            IpAddr::V6(_) => "[::]:0",
+        };
+        let socket = UdpSocket::bind(bind_addr).await?;
+        socket.connect((target, port)).await?;
+
+        let (probe, dcid) = build_quic_initial_probe();
+        socket.send(&probe).await?;
+
+        let mut buf = [0u8; 1500];
+        match timeout(Duration::from_millis(timeout_ms), socket.recv(&mut buf)).await {
+            Ok(Ok(n)) if looks_like_quic_response(&buf[..n], &dcid) => Ok(PortStatus::Open),
+            Ok(Ok(_)) => Ok(PortStatus::Filtered),
+            Ok(Err(_)) => Ok(PortStatus::Closed),
+            Err(_) => Ok(PortStatus::Filtered),
+        }
+    }
+}
+
+/// Built-in QUIC/HTTP3 service detection plugin - labels a QUIC-responsive
+/// UDP port explicitly rather than leaving it as an unidentified open port.
+pub struct QuicServicePlugin {
+    name: String,
+}
+
+impl QuicServicePlugin {
+    pub fn new() -> Self {
+        Self {
+            name: "quic_service".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ServiceDetectionPlugin for QuicServicePlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supported_ports(&self) -> Vec<u16> {
+        vec![443, 8443]
+    }
+
+    async fn initialize(&mut self, _config: PluginConfig) -> Result<()> {
+        Ok(())
+    }
+
+    async fn detect_service(&self, target: IpAddr, port: u16, timeout_ms: u64) -> Result<Option<ServiceInfo>> {
+        use tokio::net::UdpSocket;
+        use tokio::time::{timeout, Duration};
+
+        let bind_addr = match target {
+            IpAddr::V4(_) => "0.0.0.0:0",
+            IpAddr::V6(_) => "[::]:0",
+        };
+        let socket = UdpSocket::bind(bind_addr).await?;
+        socket.connect((target, port)).await?;
+        let (probe, dcid) = build_quic_initial_probe();
+        socket.send(&probe).await?;
+
+        let mut buf = [0u8; 1500];
+        match timeout(Duration::from_millis(timeout_ms), socket.recv(&mut buf)).await {
+            Ok(Ok(n)) if looks_like_quic_response(&buf[..n], &dcid) => Ok(Some(ServiceInfo {
+                name: "HTTP/3 (QUIC)".to_string(),
+                version: None,
+                banner: None,
+                confidence: 0.9,
+                additional_info: std::collections::HashMap::new(),
+            })),
+            _ => Ok(None),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_tcp_connect_plugin_creation() {
         let plugin = TcpConnectPlugin::new();
@@ -312,6 +739,53 @@ mod tests {
         assert_eq!(json_plugin.name(), "json_output");
         assert_eq!(json_plugin.file_extension(), "json");
         assert_eq!(json_plugin.content_type(), "application/json");
+
+        let ws_plugin = WebSocketOutputPlugin::new();
+        assert_eq!(ws_plugin.name(), "websocket_output");
+        assert_eq!(ws_plugin.file_extension(), "jsonl");
+
+        let nats_plugin = NatsOutputPlugin::new();
+        assert_eq!(nats_plugin.name(), "nats_output");
+        assert_eq!(nats_plugin.file_extension(), "jsonl");
+    }
+
+    #[tokio::test]
+    async fn test_nats_plugin_requires_connection() {
+        let plugin = NatsOutputPlugin::new();
+        let result = MultiHostScanResult {
+            target_spec: "127.0.0.1".to_string(),
+            scan_type: ScanType::Connect,
+            start_time: chrono::Utc::now(),
+            end_time: chrono::Utc::now(),
+            total_hosts: 0,
+            total_ports: 0,
+            hosts: vec![],
+            scan_order_seed: None,
+        };
+        assert!(plugin.format_results(&result).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_nats_plugin_initialize_without_url_is_a_noop() {
+        let mut plugin = NatsOutputPlugin::new();
+        assert!(plugin.initialize(PluginConfig::default()).await.is_ok());
+        assert!(plugin.client.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_websocket_plugin_requires_url() {
+        let plugin = WebSocketOutputPlugin::new();
+        let result = MultiHostScanResult {
+            target_spec: "127.0.0.1".to_string(),
+            scan_type: ScanType::Connect,
+            start_time: chrono::Utc::now(),
+            end_time: chrono::Utc::now(),
+            total_hosts: 0,
+            total_ports: 0,
+            hosts: vec![],
+            scan_order_seed: None,
+        };
+        assert!(plugin.format_results(&result).await.is_err());
     }
     
     #[test]
@@ -325,4 +799,49 @@ mod tests {
         assert_eq!(ssh_plugin.name(), "ssh_service");
         assert!(ssh_plugin.supported_ports().contains(&22));
     }
+
+    #[test]
+    fn test_quic_plugins() {
+        let quic_scanner = QuicProbePlugin::new();
+        assert_eq!(quic_scanner.name(), "quic_probe");
+        assert!(quic_scanner.supports_scan_type(ScanType::Udp));
+        assert!(!quic_scanner.supports_scan_type(ScanType::Connect));
+
+        let quic_service = QuicServicePlugin::new();
+        assert_eq!(quic_service.name(), "quic_service");
+        assert!(quic_service.supported_ports().contains(&443));
+    }
+
+    #[test]
+    fn test_quic_probe_is_valid_long_header() {
+        let (probe, _dcid) = build_quic_initial_probe();
+        assert_eq!(probe[0] & 0x80, 0x80);
+        assert_eq!(&probe[1..5], &QUIC_VERSION_1.to_be_bytes());
+    }
+
+    #[test]
+    fn test_quic_probe_is_padded_to_min_datagram_len() {
+        let (probe, _dcid) = build_quic_initial_probe();
+        assert_eq!(probe.len(), QUIC_MIN_INITIAL_DATAGRAM_LEN);
+    }
+
+    #[test]
+    fn test_quic_response_rejects_high_bit_without_matching_dcid() {
+        let (_probe, dcid) = build_quic_initial_probe();
+        // Long header bit set, but an unrelated/garbage Destination
+        // Connection ID - must not be mistaken for a real QUIC reply.
+        let bogus = [0xC3u8, 0x00, 0x00, 0x00, 0x01, 0x08, 0, 0, 0, 0, 0, 0, 0, 0, 0x00];
+        assert!(!looks_like_quic_response(&bogus, &dcid));
+    }
+
+    #[test]
+    fn test_quic_response_accepts_initial_echoing_our_dcid() {
+        let (_probe, dcid) = build_quic_initial_probe();
+        let mut reply = vec![0xC3u8];
+        reply.extend_from_slice(&QUIC_VERSION_1.to_be_bytes());
+        reply.push(dcid.len() as u8);
+        reply.extend_from_slice(&dcid);
+        reply.push(0); // empty Source Connection ID
+        assert!(looks_like_quic_response(&reply, &dcid));
+    }
 }
\ No newline at end of file
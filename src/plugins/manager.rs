@@ -1,11 +1,47 @@
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use anyhow::Result;
 use tokio::sync::RwLock;
 
-use super::{ScannerPlugin, OutputPlugin, ServiceDetectionPlugin, PluginConfig, PluginMetadata, PluginError, PluginResult};
+use super::{
+    ScannerPlugin, OutputPlugin, ServiceDetectionPlugin, PluginConfig, PluginMetadata,
+    PluginError, PluginResult, PluginRegisterFn, RegisteredPlugin, PLUGIN_ABI_VERSION,
+    PLUGIN_ENTRY_SYMBOL,
+};
 use crate::cli::ScanType;
 
+/// Major version a plugin must declare for this build of the host to accept
+/// it; any other major version is rejected. Unlike
+/// `PLUGIN_ABI_VERSION` (which guards the binary trait-object layout), this is
+/// a semver-ish compatibility check on the plugin's own declared `version`,
+/// so a plugin built for a much older/newer host release can be rejected with
+/// a clear error instead of misbehaving at runtime.
+const MIN_PLUGIN_VERSION_MAJOR: u32 = 1;
+
+fn major_version(version: &str) -> Option<u32> {
+    version.split('.').next()?.parse().ok()
+}
+
+fn check_version_compatible(metadata: &PluginMetadata) -> PluginResult<()> {
+    match major_version(&metadata.version) {
+        Some(major) if major == MIN_PLUGIN_VERSION_MAJOR => Ok(()),
+        _ => Err(PluginError::VersionMismatch {
+            expected: format!("{}.x", MIN_PLUGIN_VERSION_MAJOR),
+            actual: metadata.version.clone(),
+        }),
+    }
+}
+
+/// An external plugin library that's been opened and had its entry point
+/// called, but not yet registered - kept around long enough for dependency
+/// resolution to decide whether (and when) it can be registered.
+struct OpenedPlugin {
+    path: std::path::PathBuf,
+    library: libloading::Library,
+    registered: Box<RegisteredPlugin>,
+}
+
 /// Plugin manager handles loading, managing, and executing plugins
 pub struct PluginManager {
     scanner_plugins: HashMap<String, Arc<dyn ScannerPlugin>>,
@@ -13,6 +49,11 @@ pub struct PluginManager {
     service_plugins: HashMap<String, Arc<dyn ServiceDetectionPlugin>>,
     plugin_configs: HashMap<String, PluginConfig>,
     metadata: HashMap<String, PluginMetadata>,
+    // Libraries loaded for external plugins. Kept alive for the lifetime of the
+    // manager so the trait objects handed back from them stay valid - dropping
+    // one of these while its trait object is still registered would leave a
+    // dangling vtable pointer.
+    loaded_libraries: Vec<libloading::Library>,
 }
 
 impl PluginManager {
@@ -24,6 +65,7 @@ impl PluginManager {
             service_plugins: HashMap::new(),
             plugin_configs: HashMap::new(),
             metadata: HashMap::new(),
+            loaded_libraries: Vec::new(),
         }
     }
     
@@ -34,11 +76,11 @@ impl PluginManager {
         config: PluginConfig,
     ) -> PluginResult<()> {
         let name = plugin.name().to_string();
-        
-        // Initialize the plugin with its configuration
-        // Note: We can't call initialize here due to the trait constraint, 
-        // but in a real implementation we'd handle this properly
-        
+
+        // `initialize` takes `&mut self`, so it must be called by the caller
+        // while the plugin is still an exclusively-owned `Box` - by the time
+        // it reaches here it's already wrapped in the `Arc` every registered
+        // plugin is dispatched through.
         self.scanner_plugins.insert(name.clone(), plugin);
         self.plugin_configs.insert(name, config);
         
@@ -112,6 +154,25 @@ impl PluginManager {
     pub fn update_plugin_config(&mut self, name: String, config: PluginConfig) {
         self.plugin_configs.insert(name, config);
     }
+
+    /// Apply a diff of plugin configs (e.g. reloaded from disk) in place:
+    /// updates `enabled`/`priority`/`settings` for already-registered plugins
+    /// and stores configs for names not yet seen, without touching anything
+    /// else in `new_configs` doesn't mention.
+    pub fn apply_plugin_configs(&mut self, new_configs: &HashMap<String, PluginConfig>) {
+        for (name, new_config) in new_configs {
+            match self.plugin_configs.get_mut(name) {
+                Some(existing) => {
+                    existing.enabled = new_config.enabled;
+                    existing.priority = new_config.priority;
+                    existing.settings = new_config.settings.clone();
+                }
+                None => {
+                    self.plugin_configs.insert(name.clone(), new_config.clone());
+                }
+            }
+        }
+    }
     
     /// Enable a plugin
     pub fn enable_plugin(&mut self, name: &str) -> PluginResult<()> {
@@ -161,7 +222,35 @@ impl PluginManager {
         plugins.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by priority descending
         plugins
     }
-    
+
+    /// Get output plugins sorted by priority (highest first), enabled only.
+    pub fn get_prioritized_output_plugins(&self) -> Vec<(Arc<dyn OutputPlugin>, u32)> {
+        let mut plugins: Vec<_> = self.output_plugins
+            .iter()
+            .filter_map(|(name, plugin)| {
+                let config = self.plugin_configs.get(name)?;
+                config.enabled.then(|| (plugin.clone(), config.priority))
+            })
+            .collect();
+
+        plugins.sort_by(|a, b| b.1.cmp(&a.1));
+        plugins
+    }
+
+    /// Get service detection plugins sorted by priority (highest first), enabled only.
+    pub fn get_prioritized_service_plugins(&self) -> Vec<(Arc<dyn ServiceDetectionPlugin>, u32)> {
+        let mut plugins: Vec<_> = self.service_plugins
+            .iter()
+            .filter_map(|(name, plugin)| {
+                let config = self.plugin_configs.get(name)?;
+                config.enabled.then(|| (plugin.clone(), config.priority))
+            })
+            .collect();
+
+        plugins.sort_by(|a, b| b.1.cmp(&a.1));
+        plugins
+    }
+
     /// Shutdown all plugins
     pub async fn shutdown_all(&self) -> Result<()> {
         // Shutdown scanner plugins
@@ -170,40 +259,219 @@ impl PluginManager {
                 eprintln!("Warning: Failed to shutdown scanner plugin {}: {}", plugin.name(), e);
             }
         }
-        
+
         // In a real implementation, we'd also shutdown output and service plugins
         // that implement a shutdown method
-        
+
+        // `loaded_libraries` is left in place here; external plugin libraries are
+        // unloaded only when the manager itself is dropped.
         Ok(())
     }
-    
+
     /// Load plugins from configuration
     pub async fn load_from_config(&mut self, config: &crate::config::Config) -> Result<()> {
         // Load built-in plugins
         self.load_builtin_plugins().await?;
-        
-        // In a real implementation, we'd also:
-        // 1. Load external plugins from shared libraries
-        // 2. Parse plugin configuration from files
-        // 3. Validate plugin dependencies
-        // 4. Handle plugin versioning
-        
+
+        // Load any external plugins declared via the config's plugin directory/paths.
+        self.load_external_plugins(config).await?;
+
         Ok(())
     }
-    
-    /// Load built-in plugins
+
+    /// Load external plugins from shared libraries (`.so`/`.dll`/`.dylib`).
+    ///
+    /// Each candidate library must export an unmangled `extern "C"` symbol named
+    /// [`PLUGIN_ENTRY_SYMBOL`] returning a `*mut RegisteredPlugin`. The ABI version
+    /// embedded in that struct is checked against [`PLUGIN_ABI_VERSION`], and the
+    /// plugin's own declared `version` against [`check_version_compatible`], before
+    /// a plugin is considered for registration. Plugins that declare `dependencies`
+    /// on other plugin names are registered in dependency order; a whole batch of
+    /// libraries is opened up front so one can depend on another loaded later in
+    /// directory order. Anything left unresolved once no further progress can be
+    /// made is reported as `PluginError::MissingDependency`.
+    async fn load_external_plugins(&mut self, config: &crate::config::Config) -> Result<()> {
+        let mut candidates: Vec<std::path::PathBuf> = config.plugins.paths.clone();
+
+        if let Some(dir) = &config.plugins.plugins_dir {
+            if dir.is_dir() {
+                for entry in std::fs::read_dir(dir)? {
+                    let path = entry?.path();
+                    let is_lib = matches!(
+                        path.extension().and_then(|e| e.to_str()),
+                        Some("so") | Some("dll") | Some("dylib")
+                    );
+                    if is_lib {
+                        candidates.push(path);
+                    }
+                }
+            }
+        }
+
+        let mut pending = Vec::new();
+        for path in candidates {
+            match Self::open_external_plugin(&path) {
+                Ok(opened) => pending.push(opened),
+                Err(e) => eprintln!("Warning: Failed to load plugin {}: {}", path.display(), e),
+            }
+        }
+
+        while !pending.is_empty() {
+            let mut next_round = Vec::new();
+            let mut registered_any = false;
+
+            for opened in pending {
+                let known_names = self.list_plugins();
+                let unmet_dependency = opened
+                    .registered
+                    .metadata
+                    .dependencies
+                    .iter()
+                    .find(|dep| !known_names.contains(dep))
+                    .cloned();
+
+                match unmet_dependency {
+                    Some(dep) => next_round.push((opened, dep)),
+                    None => {
+                        let path = opened.path.clone();
+                        if let Err(e) = self.finish_loading_plugin(opened, &config.plugins.plugin_configs).await {
+                            eprintln!("Warning: Failed to load plugin {}: {}", path.display(), e);
+                        }
+                        registered_any = true;
+                    }
+                }
+            }
+
+            if !registered_any {
+                for (opened, dep) in next_round {
+                    eprintln!(
+                        "Warning: Failed to load plugin {}: {}",
+                        opened.path.display(),
+                        PluginError::MissingDependency(dep)
+                    );
+                }
+                break;
+            }
+
+            pending = next_round.into_iter().map(|(opened, _)| opened).collect();
+        }
+
+        Ok(())
+    }
+
+    /// Open a single external plugin library and call its entry point,
+    /// validating ABI and declared-version compatibility, without
+    /// registering it yet.
+    fn open_external_plugin(path: &Path) -> PluginResult<OpenedPlugin> {
+        let path_str = path.display().to_string();
+
+        // SAFETY: we require external plugins to export a well-known, stable
+        // `extern "C"` entry point; the library is kept alive in `loaded_libraries`
+        // for as long as any trait object obtained from it may be called.
+        let library = unsafe { libloading::Library::new(path) }
+            .map_err(|source| PluginError::LoadFailed { path: path_str.clone(), source })?;
+
+        let registered = unsafe {
+            let entry: libloading::Symbol<PluginRegisterFn> = library
+                .get(PLUGIN_ENTRY_SYMBOL)
+                .map_err(|source| PluginError::MissingEntrySymbol { path: path_str.clone(), source })?;
+
+            let raw = entry();
+            if raw.is_null() {
+                return Err(PluginError::InitializationFailed(format!(
+                    "{} returned a null plugin",
+                    path_str
+                )));
+            }
+            Box::from_raw(raw)
+        };
+
+        if registered.abi_version != PLUGIN_ABI_VERSION {
+            return Err(PluginError::AbiMismatch {
+                expected: PLUGIN_ABI_VERSION,
+                actual: registered.abi_version,
+            });
+        }
+
+        check_version_compatible(&registered.metadata)?;
+
+        Ok(OpenedPlugin { path: path.to_path_buf(), library, registered })
+    }
+
+    /// Register an opened, dependency-resolved external plugin, initializing
+    /// it (while it's still exclusively owned) with its configured settings
+    /// or a default, and keeping its library alive for the manager's lifetime.
+    async fn finish_loading_plugin(
+        &mut self,
+        opened: OpenedPlugin,
+        plugin_configs: &HashMap<String, PluginConfig>,
+    ) -> PluginResult<()> {
+        let OpenedPlugin { library, registered, .. } = opened;
+        // Keep the library mapped for the lifetime of the manager so the trait
+        // objects it produced remain valid, even if a later step below fails
+        // after one of them was already registered.
+        self.loaded_libraries.push(library);
+        let metadata = registered.metadata.clone();
+        let config = plugin_configs.get(&metadata.name).cloned().unwrap_or_default();
+
+        if let Some(mut scanner) = registered.scanner {
+            scanner.initialize(config.clone()).await
+                .map_err(|e| PluginError::InitializationFailed(e.to_string()))?;
+            self.register_scanner_plugin(Arc::from(scanner), config.clone()).await?;
+        }
+        if let Some(mut output) = registered.output {
+            output.initialize(config.clone()).await
+                .map_err(|e| PluginError::InitializationFailed(e.to_string()))?;
+            self.register_output_plugin(Arc::from(output), config.clone()).await?;
+        }
+        if let Some(mut service) = registered.service {
+            service.initialize(config.clone()).await
+                .map_err(|e| PluginError::InitializationFailed(e.to_string()))?;
+            self.register_service_plugin(Arc::from(service), config).await?;
+        }
+
+        self.metadata.insert(metadata.name.clone(), metadata);
+
+        Ok(())
+    }
+
+    /// Load built-in plugins, initializing each with its default config
+    /// before handing it to the manager.
     async fn load_builtin_plugins(&mut self) -> Result<()> {
-        // Load built-in scanner plugins
-        let tcp_plugin = Arc::new(super::builtin::TcpConnectPlugin::new());
-        self.register_scanner_plugin(tcp_plugin, PluginConfig::default()).await?;
-        
-        // Load built-in output plugins
-        let human_output = Arc::new(super::builtin::HumanOutputPlugin::new());
-        self.register_output_plugin(human_output, PluginConfig::default()).await?;
-        
-        let json_output = Arc::new(super::builtin::JsonOutputPlugin::new());
-        self.register_output_plugin(json_output, PluginConfig::default()).await?;
-        
+        macro_rules! load_scanner {
+            ($ctor:expr) => {{
+                let mut plugin = $ctor;
+                plugin.initialize(PluginConfig::default()).await?;
+                self.register_scanner_plugin(Arc::new(plugin), PluginConfig::default()).await?;
+            }};
+        }
+        macro_rules! load_output {
+            ($ctor:expr) => {{
+                let mut plugin = $ctor;
+                plugin.initialize(PluginConfig::default()).await?;
+                self.register_output_plugin(Arc::new(plugin), PluginConfig::default()).await?;
+            }};
+        }
+        macro_rules! load_service {
+            ($ctor:expr) => {{
+                let mut plugin = $ctor;
+                plugin.initialize(PluginConfig::default()).await?;
+                self.register_service_plugin(Arc::new(plugin), PluginConfig::default()).await?;
+            }};
+        }
+
+        load_scanner!(super::builtin::TcpConnectPlugin::new());
+        load_scanner!(super::builtin::QuicProbePlugin::new());
+
+        load_output!(super::builtin::HumanOutputPlugin::new());
+        load_output!(super::builtin::JsonOutputPlugin::new());
+        load_output!(super::builtin::XmlOutputPlugin::new());
+        load_output!(super::builtin::CsvOutputPlugin::new());
+        load_output!(super::builtin::WebSocketOutputPlugin::new());
+        load_output!(super::builtin::NatsOutputPlugin::new());
+
+        load_service!(super::builtin::QuicServicePlugin::new());
+
         Ok(())
     }
 }
@@ -237,7 +505,21 @@ impl ThreadSafePluginManager {
     pub async fn get_scanner_plugin(&self, name: &str) -> Option<Arc<dyn ScannerPlugin>> {
         self.inner.read().await.get_scanner_plugin(name)
     }
-    
+
+    pub async fn get_output_plugin(&self, name: &str) -> Option<Arc<dyn OutputPlugin>> {
+        self.inner.read().await.get_output_plugin(name)
+    }
+
+    pub async fn get_service_plugin(&self, name: &str) -> Option<Arc<dyn ServiceDetectionPlugin>> {
+        self.inner.read().await.get_service_plugin(name)
+    }
+
+    /// Enabled service detection plugins, highest priority first - what a
+    /// live scan should walk when its own built-in detection comes up empty.
+    pub async fn get_prioritized_service_plugins(&self) -> Vec<(Arc<dyn ServiceDetectionPlugin>, u32)> {
+        self.inner.read().await.get_prioritized_service_plugins()
+    }
+
     pub async fn get_scanner_plugins_for_type(&self, scan_type: ScanType) -> Vec<Arc<dyn ScannerPlugin>> {
         self.inner.read().await.get_scanner_plugins_for_type(scan_type)
     }
@@ -253,6 +535,71 @@ impl ThreadSafePluginManager {
     pub async fn shutdown_all(&self) -> Result<()> {
         self.inner.read().await.shutdown_all().await
     }
+
+    /// Watch `config_path` for changes and hot-apply per-plugin config edits
+    /// (`enabled`, `priority`, `settings`) without tearing down or reloading
+    /// any plugin. A malformed edit is logged and ignored, leaving the
+    /// currently-running config untouched.
+    pub fn watch_config(&self, config_path: std::path::PathBuf) {
+        let inner = self.inner.clone();
+
+        tokio::spawn(async move {
+            use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+            let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+            let mut watcher = match RecommendedWatcher::new(
+                move |res: notify::Result<notify::Event>| {
+                    if let Ok(event) = res {
+                        let _ = tx.blocking_send(event);
+                    }
+                },
+                notify::Config::default(),
+            ) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    eprintln!("Warning: failed to create plugin config watcher: {}", e);
+                    return;
+                }
+            };
+
+            if let Err(e) = watcher.watch(&config_path, RecursiveMode::NonRecursive) {
+                eprintln!("Warning: failed to watch {}: {}", config_path.display(), e);
+                return;
+            }
+
+            while let Some(event) = rx.recv().await {
+                if !matches!(
+                    event.kind,
+                    notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+                ) {
+                    continue;
+                }
+
+                if let Err(e) = Self::reload_plugin_configs(&inner, &config_path).await {
+                    eprintln!(
+                        "Warning: plugin config reload from {} failed, keeping current config: {}",
+                        config_path.display(),
+                        e
+                    );
+                }
+            }
+        });
+    }
+
+    async fn reload_plugin_configs(
+        inner: &Arc<RwLock<PluginManager>>,
+        config_path: &std::path::Path,
+    ) -> Result<()> {
+        let content = tokio::fs::read_to_string(config_path).await?;
+        let new_config = crate::config::Config::parse_str(&content, config_path)?;
+        new_config.validate()?;
+
+        // Hold the write guard only long enough to apply the diff.
+        let mut manager = inner.write().await;
+        manager.apply_plugin_configs(&new_config.plugins.plugin_configs);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -275,4 +622,42 @@ mod tests {
         assert!(plugins.contains(&"human_output".to_string()));
         assert!(plugins.contains(&"json_output".to_string()));
     }
+
+    #[test]
+    fn test_version_compatibility_check() {
+        let compatible = PluginMetadata {
+            name: "example".to_string(),
+            version: "1.3.0".to_string(),
+            description: String::new(),
+            author: String::new(),
+            plugin_type: super::super::PluginType::Scanner,
+            dependencies: Vec::new(),
+        };
+        assert!(check_version_compatible(&compatible).is_ok());
+
+        let incompatible = PluginMetadata {
+            version: "2.0.0".to_string(),
+            ..compatible
+        };
+        assert!(matches!(
+            check_version_compatible(&incompatible),
+            Err(PluginError::VersionMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_prioritized_plugins_sorted_and_filter_disabled() {
+        let mut manager = PluginManager::new();
+        manager.load_builtin_plugins().await.unwrap();
+
+        manager.update_plugin_config("human_output".to_string(), PluginConfig { priority: 10, ..PluginConfig::default() });
+        manager.update_plugin_config("json_output".to_string(), PluginConfig { priority: 200, ..PluginConfig::default() });
+        manager.update_plugin_config("xml_output".to_string(), PluginConfig { enabled: false, ..PluginConfig::default() });
+
+        let prioritized = manager.get_prioritized_output_plugins();
+        let names: Vec<&str> = prioritized.iter().map(|(p, _)| p.name()).collect();
+
+        assert_eq!(names.first(), Some(&"json_output"));
+        assert!(!names.contains(&"xml_output"));
+    }
 }
\ No newline at end of file
@@ -3,6 +3,8 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[allow(unused_imports)]
+use libloading as _;
 
 use crate::scanner::PortStatus;
 use crate::cli::ScanType;
@@ -141,11 +143,51 @@ pub enum PluginError {
     
     #[error("Plugin dependency missing: {0}")]
     MissingDependency(String),
+
+    #[error("Plugin ABI mismatch: expected {expected}, got {actual}")]
+    AbiMismatch { expected: u32, actual: u32 },
+
+    #[error("Failed to load plugin library {path}: {source}")]
+    LoadFailed {
+        path: String,
+        #[source]
+        source: libloading::Error,
+    },
+
+    #[error("Plugin entry symbol missing in {path}: {source}")]
+    MissingEntrySymbol {
+        path: String,
+        #[source]
+        source: libloading::Error,
+    },
 }
 
 /// Result type for plugin operations
 pub type PluginResult<T> = Result<T, PluginError>;
 
+/// ABI version external plugins must agree on. Bump this whenever the
+/// `ScannerPlugin`/`OutputPlugin`/`ServiceDetectionPlugin` trait shapes
+/// (or `RegisteredPlugin`'s layout) change in a way that would break a
+/// `cdylib` built against an older version of this crate.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Symbol name every external plugin library must export.
+pub const PLUGIN_ENTRY_SYMBOL: &[u8] = b"_mlscan_plugin_register";
+
+/// What an external plugin's entry point hands back. Exactly one of the
+/// trait-object fields is expected to be populated, matching `metadata.plugin_type`.
+pub struct RegisteredPlugin {
+    pub abi_version: u32,
+    pub metadata: PluginMetadata,
+    pub scanner: Option<Box<dyn ScannerPlugin>>,
+    pub output: Option<Box<dyn OutputPlugin>>,
+    pub service: Option<Box<dyn ServiceDetectionPlugin>>,
+}
+
+/// Signature of the `extern "C"` entry point an external `cdylib` must export
+/// under the name [`PLUGIN_ENTRY_SYMBOL`].
+pub type PluginRegisterFn = unsafe extern "C" fn() -> *mut RegisteredPlugin;
+
 #[cfg(test)]
 mod tests {
     use super::*;
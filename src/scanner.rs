@@ -2,23 +2,79 @@ pub mod tcp;
 mod udp;
 mod results;
 mod discovery;
+mod ml_classifier;
 mod service_detection;
 
 use anyhow::Result;
 use std::net::IpAddr;
 use std::sync::Arc;
-use tokio::sync::Semaphore;
 use tokio::time::{sleep, Duration};
-use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
+use rlimit::Resource;
+use tracing::warn;
 
-use crate::cli::ScanType;
+use crate::cli::{ScanType, ScanOrder};
 use crate::utils::parse_ports;
-use crate::network::parse_targets;
+use crate::network::{parse_targets, ResolvedTarget};
 use crate::adaptive::{AdaptiveLearning, ScanLearningData, PortScanResult, classify_network};
+use crate::hooks::{HookEvent, HookRunner};
+use crate::plugins::manager::ThreadSafePluginManager;
+use crate::proxy::ProxyConfig;
+use crate::storage::SqliteStorage;
 pub use results::{ScanResult, PortStatus, PortResult, MultiHostScanResult, ServiceInfo};
 use service_detection::ServiceDetector;
 
+/// Ask every enabled `ServiceDetectionPlugin` registered in `manager`,
+/// highest priority first, stopping at the first one that identifies
+/// something. Only reached once the built-in banner/TLS detection in
+/// `ServiceDetector` has already come up empty.
+async fn detect_service_via_plugins(
+    manager: &ThreadSafePluginManager,
+    target: IpAddr,
+    port: u16,
+    timeout_ms: u64,
+) -> Option<ServiceInfo> {
+    for (plugin, _priority) in manager.get_prioritized_service_plugins().await {
+        if !plugin.supported_ports().is_empty() && !plugin.supported_ports().contains(&port) {
+            continue;
+        }
+        if let Ok(Some(info)) = plugin.detect_service(target, port, timeout_ms).await {
+            return Some(info);
+        }
+    }
+    None
+}
+
+/// Hash a target IP into a `u64`, used to derive a per-host shuffle seed
+/// from the single recorded scan seed so hosts don't all get an identical
+/// port order while the whole run still replays from one recorded value.
+fn host_ip_hash(ip: IpAddr) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    ip.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// FDs reserved for stdio, the SQLite storage pool, config file watchers,
+/// etc. so the concurrency ceiling leaves headroom rather than spending the
+/// entire ulimit on scan sockets.
+const FD_HEADROOM: u64 = 64;
+
+/// Query the process' `RLIMIT_NOFILE`, best-effort raise the soft limit
+/// toward the hard limit (a wide scan is exactly the case this exists for),
+/// and return how many concurrent probe sockets that leaves after headroom.
+fn compute_fd_ceiling() -> usize {
+    let (soft, hard) = rlimit::getrlimit(Resource::NOFILE).unwrap_or((1024, 1024));
+
+    if soft < hard {
+        let _ = rlimit::setrlimit(Resource::NOFILE, hard, hard);
+    }
+    let (soft, _) = rlimit::getrlimit(Resource::NOFILE).unwrap_or((soft, hard));
+
+    soft.saturating_sub(FD_HEADROOM).max(1) as usize
+}
+
 /// Check if IP is in private/local range for optimized scanning
 fn is_private_ip(ip: IpAddr) -> bool {
     match ip {
@@ -49,6 +105,19 @@ pub struct Scanner {
     parallel_hosts: usize,
     adaptive_learning: AdaptiveLearning,
     service_detector: ServiceDetector,
+    hooks: HookRunner,
+    proxy: Option<ProxyConfig>,
+    send_proxy_protocol: bool,
+    scan_order: ScanOrder,
+    scan_seed: Option<u64>,
+    max_concurrent_probes: usize,
+    // This host's share of `max_concurrent_probes`, once `scan()` has
+    // divided the FD ceiling across the hosts it's running concurrently.
+    // Defaults to the full ceiling for single-host paths (`scan_unix_socket`
+    // never sets it) where there's no sibling host concurrency to share with.
+    per_host_probe_ceiling: usize,
+    plugin_manager: Option<Arc<ThreadSafePluginManager>>,
+    storage: Option<Arc<SqliteStorage>>,
 }
 
 impl Scanner {
@@ -59,8 +128,92 @@ impl Scanner {
             parallel_hosts,
             adaptive_learning: AdaptiveLearning::new(),
             service_detector: ServiceDetector::new(),
+            hooks: HookRunner::default(),
+            proxy: None,
+            send_proxy_protocol: false,
+            scan_order: ScanOrder::default(),
+            scan_seed: None,
+            max_concurrent_probes: compute_fd_ceiling(),
+            per_host_probe_ceiling: compute_fd_ceiling(),
+            plugin_manager: None,
+            storage: None,
         }
     }
+
+    /// The largest number of sockets this scanner will hold open at once,
+    /// derived from `RLIMIT_NOFILE` at construction time. Host-level
+    /// concurrency is clamped to this directly, and it's then divided across
+    /// those concurrent hosts (see `per_host_probe_ceiling` in `scan()`) so
+    /// that host-level and port-level concurrency multiplied together - the
+    /// actual peak socket count - can't exceed it and start misreporting
+    /// open ports as closed/filtered.
+    pub fn max_concurrent_probes(&self) -> usize {
+        self.max_concurrent_probes
+    }
+
+    /// Attach a hook runner so scan lifecycle events fire configured commands.
+    pub fn with_hooks(mut self, hooks: HookRunner) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Route TCP connect scans through a SOCKS5 proxy. Has no effect on the
+    /// raw-socket SYN/FIN/XMAS/NULL scans or on UDP scans.
+    pub fn with_proxy(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Send a PROXY protocol v2 header right after each TCP connect scan
+    /// connection is established, for targets behind a load balancer that
+    /// requires one.
+    pub fn with_proxy_protocol(mut self, enabled: bool) -> Self {
+        self.send_proxy_protocol = enabled;
+        self
+    }
+
+    /// Apply `learning_rate`/`min_scans_for_optimization` from `AdaptiveConfig`
+    /// to the adaptive-timeout learner, without discarding learned history.
+    pub fn with_adaptive_config(mut self, config: &crate::config::AdaptiveConfig) -> Self {
+        self.adaptive_learning.configure(config);
+        self
+    }
+
+    /// Attach a plugin manager so service detection falls back to enabled
+    /// `ServiceDetectionPlugin`s (ordered by priority) once the built-in
+    /// banner/TLS probing in `ServiceDetector` fails to identify a port.
+    pub fn with_plugin_manager(mut self, plugin_manager: Arc<ThreadSafePluginManager>) -> Self {
+        self.plugin_manager = Some(plugin_manager);
+        self
+    }
+
+    /// Persist every probed port (and roll up per-`(ip, port)` intelligence)
+    /// to `storage` as the scan runs, so `StorageBackend::Sqlite` actually
+    /// accumulates history for the adaptive subsystem to read back later.
+    pub fn with_storage(mut self, storage: Option<Arc<SqliteStorage>>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Set the port probe order. `seed` is only used by `ScanOrder::Random`;
+    /// pass `None` there to have a fresh seed generated and recorded on the
+    /// resulting `MultiHostScanResult` instead.
+    pub fn with_scan_order(mut self, order: ScanOrder, seed: Option<u64>) -> Self {
+        self.scan_order = order;
+        self.scan_seed = seed;
+        self
+    }
+
+    /// Apply scanning defaults and hooks from a (possibly hot-reloaded)
+    /// `Config`, in place. Used by `--watch` mode so a long-running scan
+    /// picks up edits to `scanning.*`/`hooks`/`adaptive.*` without restarting.
+    pub fn apply_config(&mut self, config: &crate::config::Config) {
+        self.rate_limit = config.scanning.default_rate_limit;
+        self.timeout = config.scanning.default_timeout;
+        self.parallel_hosts = config.scanning.default_parallelism;
+        self.hooks = HookRunner::new(config.hooks.clone());
+        self.adaptive_learning.configure(&config.adaptive);
+    }
     
     pub async fn scan(
         &mut self,
@@ -68,9 +221,23 @@ impl Scanner {
         ports: &str,
         scan_type: ScanType,
     ) -> Result<MultiHostScanResult> {
+        if let Some(path) = target.strip_prefix("unix:") {
+            return self.scan_unix_socket(path, scan_type).await;
+        }
+
         let targets = parse_targets(target)?;
         let port_list = parse_ports(ports)?;
-        
+
+        // Resolve the seed once per scan (not per host) so the whole run
+        // can be replayed with `--scan-seed`, and record it even when the
+        // caller didn't supply one.
+        let resolved_seed = match self.scan_order {
+            ScanOrder::Random => Some(self.scan_seed.unwrap_or_else(rand::random)),
+            ScanOrder::Serial | ScanOrder::Adaptive => None,
+        };
+
+        self.hooks.fire(HookEvent::ScanStarted);
+
         let total_operations = targets.len() * port_list.len();
         let pb = ProgressBar::new(total_operations as u64);
         pb.set_style(
@@ -80,41 +247,59 @@ impl Scanner {
         );
         
         let start_time = chrono::Utc::now();
-        
-        // Create host scanning tasks for parallel execution
-        let host_semaphore = Arc::new(Semaphore::new(self.parallel_hosts));
-        let mut host_tasks = Vec::new();
-        
-        for target_ip in targets {
-            let semaphore = host_semaphore.clone();
+
+        let host_concurrency = self.parallel_hosts.max(1).min(self.max_concurrent_probes);
+        if self.parallel_hosts > host_concurrency {
+            warn!(
+                "requested parallel_hosts={} exceeds the FD-derived ceiling of {}; throttling",
+                self.parallel_hosts, self.max_concurrent_probes
+            );
+        }
+
+        // Each of the `host_concurrency` hosts running at once gets its own
+        // share of `max_concurrent_probes` for port-level concurrency, so the
+        // product of the two layers - the actual peak open-socket count -
+        // stays under the FD ceiling instead of each layer independently
+        // being allowed to reach it.
+        let per_host_probe_ceiling = (self.max_concurrent_probes / host_concurrency).max(1);
+
+        // Stream over hosts with at most `parallel_hosts` in flight at once,
+        // rather than materializing one `JoinHandle` per host up front - for
+        // a /16 that's tens of thousands of owned `Scanner` clones and task
+        // objects sitting in a `Vec` before a single one finishes.
+        let host_futures = targets.into_iter().map(|target| {
             let port_list = port_list.clone();
             let pb = pb.clone();
-            let scan_type = scan_type;
-            
-            let task = {
-                let mut scanner_clone = Scanner::new(self.rate_limit, self.timeout, self.parallel_hosts);
-                scanner_clone.adaptive_learning = self.adaptive_learning.clone();
-                scanner_clone.service_detector = ServiceDetector::new();
-                
-                tokio::spawn(async move {
-                    let _permit = semaphore.acquire().await.unwrap();
-                    scanner_clone.scan_single_host(target_ip, &port_list, scan_type, pb).await
-                })
-            };
-            
-            host_tasks.push(task);
-        }
-        
-        // Wait for all host scans to complete
-        let host_results: Vec<ScanResult> = join_all(host_tasks).await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?
+
+            let mut scanner_clone = Scanner::new(self.rate_limit, self.timeout, self.parallel_hosts);
+            scanner_clone.adaptive_learning = self.adaptive_learning.clone();
+            scanner_clone.service_detector = ServiceDetector::new();
+            scanner_clone.hooks = self.hooks.clone();
+            scanner_clone.proxy = self.proxy.clone();
+            scanner_clone.send_proxy_protocol = self.send_proxy_protocol;
+            scanner_clone.scan_order = self.scan_order;
+            scanner_clone.scan_seed = resolved_seed;
+            scanner_clone.max_concurrent_probes = self.max_concurrent_probes;
+            scanner_clone.per_host_probe_ceiling = per_host_probe_ceiling;
+            scanner_clone.plugin_manager = self.plugin_manager.clone();
+            scanner_clone.storage = self.storage.clone();
+
+            async move { scanner_clone.scan_single_host(target, &port_list, scan_type, pb).await }
+        });
+
+        let mut host_results: Vec<ScanResult> = stream::iter(host_futures)
+            .buffer_unordered(host_concurrency)
+            .collect::<Vec<Result<ScanResult>>>()
+            .await
             .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
-        
+            .collect::<Result<Vec<_>>>()?;
+        host_results.sort_by_key(|host| host.target_ip);
+
         pb.finish_with_message("⟦SCAN COMPLETE⟧ Network discovery finished");
         let end_time = chrono::Utc::now();
-        
+
+        self.hooks.fire(HookEvent::ScanComplete);
+
         Ok(MultiHostScanResult {
             target_spec: target.to_string(),
             scan_type,
@@ -123,18 +308,96 @@ impl Scanner {
             total_hosts: host_results.len(),
             total_ports: port_list.len(),
             hosts: host_results,
+            scan_order_seed: resolved_seed,
         })
     }
-    
+
+    /// Scan a single `unix:/path/to.sock` target. Unix domain sockets have
+    /// no port space, so this bypasses the whole host/port fan-out above and
+    /// reports reachability as a single synthetic port-0 result.
+    async fn scan_unix_socket(&mut self, path: &str, scan_type: ScanType) -> Result<MultiHostScanResult> {
+        self.hooks.fire(HookEvent::ScanStarted);
+
+        let start_time = chrono::Utc::now();
+        let status = tcp::unix_connect_scan(path, self.timeout).await;
+        let end_time = chrono::Utc::now();
+
+        let sentinel_host = IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED);
+
+        if status == PortStatus::Open {
+            self.hooks.fire(HookEvent::OpenPortFound { host: sentinel_host, port: 0, service: None });
+        }
+
+        let port_result = PortResult {
+            port: 0,
+            status,
+            is_filtered: status == PortStatus::Filtered,
+            response_time: None,
+            service_detected: None,
+            retransmits: None,
+        };
+
+        let host_result = ScanResult {
+            target: format!("unix:{}", path),
+            target_ip: sentinel_host,
+            scan_type,
+            start_time,
+            end_time,
+            ports: vec![port_result],
+        };
+
+        self.hooks.fire(HookEvent::ScanComplete);
+
+        Ok(MultiHostScanResult {
+            target_spec: format!("unix:{}", path),
+            scan_type,
+            start_time,
+            end_time,
+            total_hosts: 1,
+            total_ports: 1,
+            hosts: vec![host_result],
+            scan_order_seed: None,
+        })
+    }
+
+    /// Reorder `port_list` per `self.scan_order` before a host's ports are
+    /// probed. `Serial` is a no-op (current behavior); `Random` shuffles with
+    /// a per-host RNG derived from the recorded scan seed so the whole run
+    /// is reproducible while hosts don't all get an identical order; `Adaptive`
+    /// defers to the adaptive learner's per-network-class open-port history.
+    fn order_ports(&self, port_list: &[u16], target_ip: IpAddr) -> Vec<u16> {
+        use rand::seq::SliceRandom;
+        use rand::{Rng, SeedableRng};
+
+        match self.scan_order {
+            ScanOrder::Serial => port_list.to_vec(),
+            ScanOrder::Random => {
+                let base_seed = self.scan_seed.unwrap_or(0);
+                let mut host_seed_rng = rand::rngs::StdRng::seed_from_u64(base_seed ^ host_ip_hash(target_ip));
+                let per_host_seed: u64 = host_seed_rng.gen();
+                let mut rng = rand::rngs::StdRng::seed_from_u64(per_host_seed);
+                let mut shuffled = port_list.to_vec();
+                shuffled.shuffle(&mut rng);
+                shuffled
+            }
+            ScanOrder::Adaptive => self
+                .adaptive_learning
+                .ordered_ports_for(classify_network(target_ip), port_list),
+        }
+    }
+
     async fn scan_single_host(
         &mut self,
-        target_ip: IpAddr,
+        target: ResolvedTarget,
         port_list: &[u16],
         scan_type: ScanType,
         pb: ProgressBar,
     ) -> Result<ScanResult> {
+        let target_ip = target.ip;
+        self.hooks.fire(HookEvent::HostUp { host: target_ip });
+
         // Get optimized parameters from adaptive learning
-        let optimal_params = self.adaptive_learning.get_optimal_params(target_ip);
+        let optimal_params = self.adaptive_learning.get_optimal_params(target_ip, self.timeout);
         let adaptive_timeout = optimal_params.timeout;
         let adaptive_rate_limit = optimal_params.rate_limit;
         let adaptive_parallelism = optimal_params.parallelism as usize;
@@ -143,73 +406,127 @@ impl Scanner {
         let effective_timeout = if adaptive_timeout > 0 { adaptive_timeout } else { self.timeout };
         let effective_rate_limit = if adaptive_rate_limit > 0 { adaptive_rate_limit } else { self.rate_limit };
         let effective_parallelism = if adaptive_parallelism > 0 { adaptive_parallelism } else { self.parallel_hosts };
-        
-        let semaphore = Arc::new(Semaphore::new(effective_parallelism));
-        let mut tasks = vec![];
-        
+        let effective_parallelism = effective_parallelism.max(1).min(self.per_host_probe_ceiling);
+        if effective_parallelism == self.per_host_probe_ceiling
+            && (adaptive_parallelism.max(self.parallel_hosts)) > self.per_host_probe_ceiling
+        {
+            warn!(
+                "requested port parallelism for {} exceeds this host's share ({}) of the FD-derived ceiling ({}); throttling",
+                target_ip, self.per_host_probe_ceiling, self.max_concurrent_probes
+            );
+        }
+
         let start_time = chrono::Utc::now();
         let scan_start = std::time::Instant::now();
-        
-        for port in port_list.iter() {
-            let sem = semaphore.clone();
-            let target_ip = target_ip.clone();
-            let port = *port;
+
+        let ordered_ports = self.order_ports(port_list, target_ip);
+
+        // Stream over this host's ports with at most `effective_parallelism`
+        // probes in flight, instead of spawning one task per port up front -
+        // a full-range scan of a single host would otherwise hold 65k
+        // `JoinHandle`s in memory before the first result lands.
+        let port_futures = ordered_ports.into_iter().map(|port| {
             let timeout = effective_timeout;
             let rate_limit = effective_rate_limit;
             let pb = pb.clone();
-            
-            let task = tokio::spawn(async move {
-                let _permit = sem.acquire().await.unwrap();
-                
+            let proxy = self.proxy.clone();
+            let send_proxy_protocol = self.send_proxy_protocol;
+
+            async move {
                 let scan_start = std::time::Instant::now();
-                let result = match scan_type {
-                    ScanType::Syn => tcp::syn_scan(target_ip, port, timeout).await,
+                let (result, kernel_rtt_ms, retransmits) = match scan_type {
+                    ScanType::Syn => (tcp::syn_scan(target_ip, port, timeout).await, None, None),
                     ScanType::Connect => {
                         // Use fast connect scan for private networks
-                        if is_private_ip(target_ip) {
-                            tcp::fast_connect_scan(target_ip, port, timeout).await
+                        let probe = if is_private_ip(target_ip) {
+                            tcp::fast_connect_scan_instrumented(target_ip, port, timeout, proxy.as_ref(), send_proxy_protocol).await
                         } else {
-                            tcp::connect_scan(target_ip, port, timeout).await
-                        }
+                            tcp::connect_scan_instrumented(target_ip, port, timeout, proxy.as_ref(), send_proxy_protocol).await
+                        };
+                        (probe.status, probe.kernel_rtt_ms, probe.retransmits)
                     },
-                    ScanType::Udp => udp::udp_scan(target_ip, port, timeout).await,
-                    ScanType::Fin => tcp::fin_scan(target_ip, port, timeout).await,
-                    ScanType::Xmas => tcp::xmas_scan(target_ip, port, timeout).await,
-                    ScanType::Null => tcp::null_scan(target_ip, port, timeout).await,
+                    ScanType::Udp => (udp::udp_scan(target_ip, port, timeout).await, None, None),
+                    ScanType::Fin => (tcp::fin_scan(target_ip, port, timeout).await, None, None),
+                    ScanType::Xmas => (tcp::xmas_scan(target_ip, port, timeout).await, None, None),
+                    ScanType::Null => (tcp::null_scan(target_ip, port, timeout).await, None, None),
                 };
-                let scan_duration = scan_start.elapsed().as_millis() as f64;
-                
+                // The kernel's TCP_INFO RTT only covers actual wire time;
+                // prefer it over wall-clock elapsed, which also counts
+                // scheduling and concurrency-limit wait.
+                let scan_duration = kernel_rtt_ms.unwrap_or_else(|| scan_start.elapsed().as_millis() as f64);
+
                 pb.inc(1);
-                
+
                 if rate_limit > 0 {
                     sleep(Duration::from_millis(rate_limit)).await;
                 }
-                
-                PortResult { 
-                    port, 
+
+                PortResult {
+                    port,
                     status: result,
                     is_filtered: result == PortStatus::Filtered,
                     response_time: Some(scan_duration),
                     service_detected: None, // Will be filled in later for open ports
+                    retransmits,
                 }
-            });
-            
-            tasks.push(task);
-        }
-        
-        let mut port_results = join_all(tasks).await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
-        
+            }
+        });
+
+        let mut port_results: Vec<PortResult> = stream::iter(port_futures)
+            .buffer_unordered(effective_parallelism)
+            .collect()
+            .await;
+        port_results.sort_by_key(|p| p.port);
+
         // Perform service detection on open ports
         for port_result in &mut port_results {
             if port_result.status == PortStatus::Open {
                 port_result.service_detected = self.service_detector
-                    .detect_service(target_ip, port_result.port)
+                    .detect_service(target_ip, Some(target.original.as_str()), port_result.port)
                     .await;
+
+                // Fall back to enabled ServiceDetectionPlugins (e.g. the
+                // built-in QUIC plugin, or anything a user dropped into
+                // the plugins dir) only once the built-in probing above came
+                // up empty.
+                if port_result.service_detected.is_none() {
+                    if let Some(manager) = &self.plugin_manager {
+                        port_result.service_detected =
+                            detect_service_via_plugins(manager, target_ip, port_result.port, effective_timeout).await;
+                    }
+                }
+
+                self.hooks.fire(HookEvent::OpenPortFound {
+                    host: target_ip,
+                    port: port_result.port,
+                    service: port_result.service_detected.as_ref().map(|s| s.name.clone()),
+                });
+            }
+
+            if let Some(storage) = &self.storage {
+                let is_open = port_result.status == PortStatus::Open;
+                let is_filtered = port_result.status == PortStatus::Filtered;
+                let service_name = port_result.service_detected.as_ref().map(|s| s.name.as_str());
+                let banner = port_result.service_detected.as_ref()
+                    .and_then(|s| s.banner.as_deref())
+                    .map(str::as_bytes);
+
+                if let Err(e) = storage.record_port_result(
+                    target_ip, port_result.port, is_open, port_result.response_time, service_name, banner,
+                ).await {
+                    warn!("failed to persist scan result for {}:{}: {}", target_ip, port_result.port, e);
+                }
+
+                if let Some(response_time) = port_result.response_time {
+                    if let Err(e) = storage.upsert_port_intelligence(
+                        target_ip, port_result.port, response_time, is_open, is_filtered,
+                    ).await {
+                        warn!("failed to update port intelligence for {}:{}: {}", target_ip, port_result.port, e);
+                    }
+                }
             }
         }
-        
+
         let end_time = chrono::Utc::now();
         let scan_duration = scan_start.elapsed();
         
@@ -238,6 +555,7 @@ impl Scanner {
                 is_filtered,
                 response_time,
                 service_detected: port_result.service_detected.as_ref().map(|s| s.name.clone()),
+                retransmits: port_result.retransmits,
             });
         }
         
@@ -265,8 +583,17 @@ impl Scanner {
         // Learn from the scan results
         self.adaptive_learning.learn_from_scan(&learning_data);
         
+        // Show the original hostname alongside its resolved IP so output
+        // doesn't collapse `scanme.example.com` down to an opaque address;
+        // a literal IP/CIDR/range token has no separate hostname to show.
+        let target_label = if target.original == target_ip.to_string() {
+            target.original
+        } else {
+            format!("{} ({})", target.original, target_ip)
+        };
+
         Ok(ScanResult {
-            target: target_ip.to_string(),
+            target: target_label,
             target_ip,
             scan_type,
             start_time,
@@ -274,19 +601,4 @@ impl Scanner {
             ports: port_results,
         })
     }
-    
-    async fn resolve_target(&self, target: &str) -> Result<IpAddr> {
-        use std::net::ToSocketAddrs;
-        
-        if let Ok(ip) = target.parse::<IpAddr>() {
-            return Ok(ip);
-        }
-        
-        let addr = format!("{}:0", target)
-            .to_socket_addrs()?
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("Failed to resolve hostname"))?;
-        
-        Ok(addr.ip())
-    }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use smartcore::linalg::basic::matrix::DenseMatrix;
 use smartcore::ensemble::random_forest_classifier::RandomForestClassifier;
 use smartcore::tree::decision_tree_classifier::DecisionTreeClassifier;
+use smartcore::tree::decision_tree_regressor::{DecisionTreeRegressor, DecisionTreeRegressorParameters};
 use smartcore::neighbors::knn_classifier::KNNClassifier;
 use smartcore::svm::svc::SVC;
 use smartcore::model_selection::train_test_split;
@@ -10,6 +12,477 @@ use smartcore::preprocessing::StandardScaler;
 use ndarray::{Array1, Array2};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
+use std::path::Path;
+use wide::f64x4;
+use rustfft::{num_complex::Complex, FftPlanner};
+use rand::seq::SliceRandom;
+
+/// On-disk format version for `MLServiceClassifier` snapshots, bumped
+/// whenever the persisted field set below changes shape.
+const CLASSIFIER_SNAPSHOT_VERSION: u32 = 6;
+
+/// Kernel function of an imported libSVM model, with the parameters
+/// `svm-train` would have baked into the `.model` file's header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SvmKernel {
+    Linear,
+    Polynomial { degree: i32, gamma: f64, coef0: f64 },
+    Rbf { gamma: f64 },
+    Sigmoid { gamma: f64, coef0: f64 },
+}
+
+/// A classifier trained offline with libSVM's `svm-train` and imported
+/// verbatim, so operators can use the mature libSVM toolchain for heavy
+/// model search and still get low-latency inference inside mlscan.
+///
+/// Support vectors are stored densely (sparse `idx:value` pairs expanded
+/// against `ServiceFeatures::to_vector()`'s 29 slots) so kernel dot-products
+/// can run over packed SIMD lanes instead of walking sparse index pairs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImportedSVM {
+    svm_type: String,
+    kernel: SvmKernel,
+    nr_class: usize,
+    // One-vs-one decision function biases, nr_class*(nr_class-1)/2 of them.
+    rho: Vec<f64>,
+    // libSVM's integer class labels, in the same order as `nr_sv`.
+    labels: Vec<i32>,
+    // Number of support vectors per class, in label order.
+    nr_sv: Vec<usize>,
+    // nr_class-1 rows of per-support-vector coefficients, as laid out in
+    // the `.model` file (row `k` holds the coefficients of decision
+    // functions involving class `k+1`).
+    sv_coef: Vec<Vec<f64>>,
+    // Dense support vectors, padded/truncated to ServiceFeatures's length.
+    support_vectors: Vec<Vec<f64>>,
+    // Maps libSVM's bare integer labels back to mlscan service names; a
+    // label with no entry falls back to "svm_class_<n>". libSVM model files
+    // don't carry string labels, so this mapping is supplied by the caller.
+    label_names: HashMap<i32, String>,
+}
+
+/// Sum of lane-wise products of `a` and `b`, computed four `f64`s at a time.
+fn simd_dot(a: &[f64], b: &[f64]) -> f64 {
+    let len = a.len().min(b.len());
+    let chunks = len / 4;
+    let mut acc = f64x4::splat(0.0);
+    for i in 0..chunks {
+        let base = i * 4;
+        let va = f64x4::from([a[base], a[base + 1], a[base + 2], a[base + 3]]);
+        let vb = f64x4::from([b[base], b[base + 1], b[base + 2], b[base + 3]]);
+        acc += va * vb;
+    }
+    let mut sum: f64 = acc.to_array().iter().sum();
+    for i in (chunks * 4)..len {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+/// Squared Euclidean distance between `a` and `b`, computed the same way.
+fn simd_squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    let len = a.len().min(b.len());
+    let chunks = len / 4;
+    let mut acc = f64x4::splat(0.0);
+    for i in 0..chunks {
+        let base = i * 4;
+        let va = f64x4::from([a[base], a[base + 1], a[base + 2], a[base + 3]]);
+        let vb = f64x4::from([b[base], b[base + 1], b[base + 2], b[base + 3]]);
+        let diff = va - vb;
+        acc += diff * diff;
+    }
+    let mut sum: f64 = acc.to_array().iter().sum();
+    for i in (chunks * 4)..len {
+        let diff = a[i] - b[i];
+        sum += diff * diff;
+    }
+    sum
+}
+
+impl ImportedSVM {
+    fn kernel_value(&self, support_vector: &[f64], x: &[f64]) -> f64 {
+        match &self.kernel {
+            SvmKernel::Linear => simd_dot(support_vector, x),
+            SvmKernel::Polynomial { degree, gamma, coef0 } => {
+                (gamma * simd_dot(support_vector, x) + coef0).powi(*degree)
+            }
+            SvmKernel::Rbf { gamma } => (-gamma * simd_squared_distance(support_vector, x)).exp(),
+            SvmKernel::Sigmoid { gamma, coef0 } => {
+                (gamma * simd_dot(support_vector, x) + coef0).tanh()
+            }
+        }
+    }
+
+    /// Run libSVM's standard one-vs-one voting scheme and return the
+    /// winning class's mapped service name.
+    fn predict_value(&self, x: &[f64]) -> String {
+        if self.nr_class < 2 || self.labels.len() != self.nr_class {
+            return "unknown".to_string();
+        }
+
+        let mut start = Vec::with_capacity(self.nr_class);
+        let mut offset = 0;
+        for &count in &self.nr_sv {
+            start.push(offset);
+            offset += count;
+        }
+
+        let kvalues: Vec<f64> = self
+            .support_vectors
+            .iter()
+            .map(|sv| self.kernel_value(sv, x))
+            .collect();
+
+        let mut votes = vec![0u32; self.nr_class];
+        let mut p = 0;
+        for i in 0..self.nr_class {
+            for j in (i + 1)..self.nr_class {
+                let (si, ci) = (start[i], self.nr_sv[i]);
+                let (sj, cj) = (start[j], self.nr_sv[j]);
+
+                let mut sum = 0.0;
+                for k in 0..ci {
+                    sum += self.sv_coef[j - 1][si + k] * kvalues[si + k];
+                }
+                for k in 0..cj {
+                    sum += self.sv_coef[i][sj + k] * kvalues[sj + k];
+                }
+                sum -= self.rho.get(p).copied().unwrap_or(0.0);
+
+                if sum > 0.0 {
+                    votes[i] += 1;
+                } else {
+                    votes[j] += 1;
+                }
+                p += 1;
+            }
+        }
+
+        let winner = votes
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, v)| **v)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let label = self.labels[winner];
+        self.label_names
+            .get(&label)
+            .cloned()
+            .unwrap_or_else(|| format!("svm_class_{}", label))
+    }
+}
+
+/// Parse a libSVM `svm-train .model` text file into an `ImportedSVM`.
+///
+/// Understands the standard header (`svm_type`, `kernel_type`, `degree`,
+/// `gamma`, `coef0`, `nr_class`, `rho`, `label`, `nr_sv`) followed by the
+/// `SV` marker and one support-vector row per line (`coef...coef idx:value...`).
+fn parse_libsvm_model(content: &str) -> Result<ImportedSVM, Box<dyn std::error::Error>> {
+    let mut lines = content.lines();
+
+    let mut svm_type = String::new();
+    let mut kernel_type = String::new();
+    let mut degree = 3i32;
+    let mut gamma = 0.0f64;
+    let mut coef0 = 0.0f64;
+    let mut nr_class = 0usize;
+    let mut rho = Vec::new();
+    let mut labels = Vec::new();
+    let mut nr_sv = Vec::new();
+
+    for line in &mut lines {
+        let line = line.trim();
+        if line == "SV" {
+            break;
+        }
+        let mut parts = line.split_whitespace();
+        let key = parts.next().unwrap_or("");
+        let rest: Vec<&str> = parts.collect();
+        match key {
+            "svm_type" => svm_type = rest.first().copied().unwrap_or("").to_string(),
+            "kernel_type" => kernel_type = rest.first().copied().unwrap_or("").to_string(),
+            "degree" => degree = rest.first().and_then(|s| s.parse().ok()).unwrap_or(3),
+            "gamma" => gamma = rest.first().and_then(|s| s.parse().ok()).unwrap_or(0.0),
+            "coef0" => coef0 = rest.first().and_then(|s| s.parse().ok()).unwrap_or(0.0),
+            "nr_class" => nr_class = rest.first().and_then(|s| s.parse().ok()).unwrap_or(0),
+            "rho" => rho = rest.iter().filter_map(|s| s.parse().ok()).collect(),
+            "label" => labels = rest.iter().filter_map(|s| s.parse().ok()).collect(),
+            "nr_sv" => nr_sv = rest.iter().filter_map(|s| s.parse::<usize>().ok()).collect(),
+            _ => {}
+        }
+    }
+
+    if nr_class == 0 {
+        return Err("libsvm model missing nr_class header".into());
+    }
+
+    let kernel = match kernel_type.as_str() {
+        "linear" => SvmKernel::Linear,
+        "polynomial" => SvmKernel::Polynomial { degree, gamma, coef0 },
+        "sigmoid" => SvmKernel::Sigmoid { gamma, coef0 },
+        // libSVM's own default kernel_type is "rbf".
+        _ => SvmKernel::Rbf { gamma },
+    };
+
+    let n_features = ServiceFeatures::feature_names().len();
+    let mut sv_coef: Vec<Vec<f64>> = vec![Vec::new(); nr_class.saturating_sub(1)];
+    let mut support_vectors = Vec::new();
+
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        for coef_row in sv_coef.iter_mut() {
+            if let Some(value) = tokens.next().and_then(|t| t.parse::<f64>().ok()) {
+                coef_row.push(value);
+            }
+        }
+
+        let mut dense = vec![0.0; n_features];
+        for token in tokens {
+            if let Some((idx, val)) = token.split_once(':') {
+                if let (Ok(idx), Ok(val)) = (idx.parse::<usize>(), val.parse::<f64>()) {
+                    if idx >= 1 {
+                        let zero_based = idx - 1;
+                        if zero_based >= dense.len() {
+                            dense.resize(zero_based + 1, 0.0);
+                        }
+                        dense[zero_based] = val;
+                    }
+                }
+            }
+        }
+        support_vectors.push(dense);
+    }
+
+    Ok(ImportedSVM {
+        svm_type,
+        kernel,
+        nr_class,
+        rho,
+        labels,
+        nr_sv,
+        sv_coef,
+        support_vectors,
+        label_names: HashMap::new(),
+    })
+}
+
+/// Tunable hyperparameters for the GBDT ensemble member. Set through
+/// `MLServiceClassifier::with_gbdt_config` so callers can trade off fit time against
+/// accuracy for their own dataset size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GbdtConfig {
+    pub max_depth: u16,
+    pub min_leaf_size: usize,
+    // Fraction of the 24 (now more, with spectral features) ServiceFeatures
+    // columns sampled per boosting stage.
+    pub feature_sample_ratio: f64,
+    pub learning_rate: f64,
+    pub n_iterations: usize,
+}
+
+impl Default for GbdtConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            min_leaf_size: 5,
+            feature_sample_ratio: 0.8,
+            learning_rate: 0.1,
+            n_iterations: 50,
+        }
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// One boosting stage of a one-vs-rest GBDT binary problem: a regression
+/// tree fit over a sampled subset of feature columns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GbdtStage {
+    feature_indices: Vec<usize>,
+    tree: DecisionTreeRegressor<f64, f64>,
+}
+
+impl GbdtStage {
+    fn project(&self, x: &[f64]) -> Vec<f64> {
+        self.feature_indices.iter().map(|&idx| x.get(idx).copied().unwrap_or(0.0)).collect()
+    }
+}
+
+/// Gradient-boosted decision trees over log-likelihood loss, trained
+/// one-vs-rest across the multi-class service labels. Weak learners are
+/// `smartcore`'s `DecisionTreeRegressor`, boosted by fitting each stage to
+/// the previous stage's pseudo-residuals (`y - sigmoid(F)`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GbdtModel {
+    config: GbdtConfig,
+    classes: Vec<String>,
+    // Per-class starting log-odds, before any boosting stage is applied.
+    initial_log_odds: Vec<f64>,
+    // Per-class sequence of boosting stages.
+    stages: Vec<Vec<GbdtStage>>,
+}
+
+impl GbdtModel {
+    fn train(
+        config: &GbdtConfig,
+        features_matrix: &[Vec<f64>],
+        labels: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut classes: Vec<String> = labels.to_vec();
+        classes.sort();
+        classes.dedup();
+
+        let n_features = features_matrix.first().map(|row| row.len()).unwrap_or(0);
+        let sample_size = ((n_features as f64) * config.feature_sample_ratio)
+            .round()
+            .max(1.0) as usize;
+        let sample_size = sample_size.min(n_features.max(1));
+
+        let mut initial_log_odds = Vec::with_capacity(classes.len());
+        let mut stages: Vec<Vec<GbdtStage>> = Vec::with_capacity(classes.len());
+        let mut rng = rand::thread_rng();
+
+        for class in &classes {
+            let y: Vec<f64> = labels
+                .iter()
+                .map(|label| if label == class { 1.0 } else { 0.0 })
+                .collect();
+
+            let positive_rate = (y.iter().sum::<f64>() / y.len().max(1) as f64).clamp(1e-3, 1.0 - 1e-3);
+            let base_score = (positive_rate / (1.0 - positive_rate)).ln();
+            initial_log_odds.push(base_score);
+
+            let mut current_scores = vec![base_score; features_matrix.len()];
+            let mut class_stages = Vec::with_capacity(config.n_iterations);
+
+            for _ in 0..config.n_iterations {
+                let residuals: Vec<f64> = current_scores
+                    .iter()
+                    .zip(y.iter())
+                    .map(|(&score, &label)| label - sigmoid(score))
+                    .collect();
+
+                let all_features: Vec<usize> = (0..n_features).collect();
+                let mut feature_indices: Vec<usize> =
+                    all_features.choose_multiple(&mut rng, sample_size).copied().collect();
+                feature_indices.sort_unstable();
+
+                let projected: Vec<Vec<f64>> = features_matrix
+                    .iter()
+                    .map(|row| feature_indices.iter().map(|&idx| row[idx]).collect())
+                    .collect();
+
+                let x = DenseMatrix::from_2d_vec(&projected);
+                let params = DecisionTreeRegressorParameters {
+                    max_depth: Some(config.max_depth),
+                    min_samples_leaf: config.min_leaf_size,
+                    ..Default::default()
+                };
+                let tree = DecisionTreeRegressor::fit(&x, &residuals, params)?;
+                let predicted_residuals = tree.predict(&x)?;
+
+                for (score, predicted) in current_scores.iter_mut().zip(predicted_residuals.iter()) {
+                    *score += config.learning_rate * predicted;
+                }
+
+                class_stages.push(GbdtStage { feature_indices, tree });
+            }
+
+            stages.push(class_stages);
+        }
+
+        Ok(Self {
+            config: config.clone(),
+            classes,
+            initial_log_odds,
+            stages,
+        })
+    }
+
+    /// Per-class log-odds scores for one feature vector; the predicted
+    /// label is whichever class has the highest score.
+    fn predict_scores(&self, x: &[f64]) -> Vec<(String, f64)> {
+        self.classes
+            .iter()
+            .zip(self.initial_log_odds.iter())
+            .zip(self.stages.iter())
+            .map(|((class, &base_score), class_stages)| {
+                let mut score = base_score;
+                for stage in class_stages {
+                    let projected = stage.project(x);
+                    let projected_matrix = DenseMatrix::from_2d_vec(&vec![projected]);
+                    if let Ok(predictions) = stage.tree.predict(&projected_matrix) {
+                        if let Some(&predicted) = predictions.first() {
+                            score += self.config.learning_rate * predicted;
+                        }
+                    }
+                }
+                (class.clone(), score)
+            })
+            .collect()
+    }
+
+    fn predict_one(&self, x: &[f64]) -> Option<String> {
+        self.predict_scores(x)
+            .into_iter()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(class, _)| class)
+    }
+
+    fn predict(&self, features_matrix: &[Vec<f64>]) -> Vec<String> {
+        features_matrix
+            .iter()
+            .map(|row| self.predict_one(row).unwrap_or_else(|| "unknown".to_string()))
+            .collect()
+    }
+}
+
+/// Borrowed view of the persisted classifier state, used by `save_to_path`
+/// so writing a snapshot doesn't require cloning every fitted model.
+#[derive(Serialize)]
+struct ClassifierSnapshotRef<'a> {
+    format_version: u32,
+    random_forest: &'a Option<RandomForestClassifier<f64, String>>,
+    svm_classifier: &'a Option<SVC<f64, String>>,
+    knn_classifier: &'a Option<KNNClassifier<f64, String>>,
+    decision_tree: &'a Option<DecisionTreeClassifier<f64, String>>,
+    scaler: &'a Option<StandardScaler<f64>>,
+    model_accuracies: &'a HashMap<String, f64>,
+    service_labels: &'a Vec<String>,
+    feature_names: &'a Vec<String>,
+    imported_svm: &'a Option<ImportedSVM>,
+    naive_bayes_banner: &'a NaiveBayesBanner,
+    feature_importances: &'a HashMap<String, f64>,
+    gbdt_config: &'a GbdtConfig,
+    gbdt: &'a Option<GbdtModel>,
+    model_class_precision: &'a HashMap<String, HashMap<String, f64>>,
+}
+
+/// Owned counterpart used to deserialize a snapshot back into a classifier.
+#[derive(Deserialize)]
+struct ClassifierSnapshot {
+    format_version: u32,
+    random_forest: Option<RandomForestClassifier<f64, String>>,
+    svm_classifier: Option<SVC<f64, String>>,
+    knn_classifier: Option<KNNClassifier<f64, String>>,
+    decision_tree: Option<DecisionTreeClassifier<f64, String>>,
+    scaler: Option<StandardScaler<f64>>,
+    model_accuracies: HashMap<String, f64>,
+    service_labels: Vec<String>,
+    feature_names: Vec<String>,
+    imported_svm: Option<ImportedSVM>,
+    naive_bayes_banner: NaiveBayesBanner,
+    feature_importances: HashMap<String, f64>,
+    gbdt_config: GbdtConfig,
+    gbdt: Option<GbdtModel>,
+    model_class_precision: HashMap<String, HashMap<String, f64>>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceFeatures {
@@ -48,13 +521,36 @@ pub struct ServiceFeatures {
     pub medium_response: f64,     // 100-1000ms
     pub slow_response: f64,       // > 1000ms
     pub response_variance: f64,   // Variance across multiple probes
+
+    // Spectral timing patterns: magnitudes of the first SPECTRAL_BIN_COUNT
+    // low-frequency FFT bins over a fixed-length repeated-probe timing
+    // series, plus the spectral centroid. Periodic keep-alive/heartbeat
+    // services and rate-limited endpoints show up here even when flat
+    // variance alone misses them.
+    pub spectral_bin_0: f64,
+    pub spectral_bin_1: f64,
+    pub spectral_bin_2: f64,
+    pub spectral_bin_3: f64,
+    pub spectral_centroid: f64,
 }
 
+/// Number of repeated timing probes sampled per port when computing
+/// spectral timing features; missing/timed-out probes are zero-filled up
+/// to this length before the FFT.
+pub const SPECTRAL_PROBE_COUNT: usize = 64;
+
+/// Low-frequency magnitude bins kept as features, numbered from the first
+/// bin after DC (which de-meaning the series already zeroes out).
+pub const SPECTRAL_BIN_COUNT: usize = 4;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceClassification {
     pub service_name: String,
     pub confidence: f64,
-    pub confidence_scores: HashMap<String, f64>, // Per-algorithm confidence
+    // Calibrated posterior probability per candidate service class (sums to
+    // 1), not a per-algorithm number - so two predictions landing on
+    // different classes get genuinely different confidence.
+    pub confidence_scores: HashMap<String, f64>,
     pub feature_importance: HashMap<String, f64>,
     pub reasoning: Vec<String>,
 }
@@ -66,6 +562,106 @@ pub struct TrainingExample {
     pub target: IpAddr,
     pub port: u16,
     pub timestamp: u64,
+    // Raw banner text, kept alongside the numeric features so the Naive
+    // Bayes token model below has something to train on.
+    pub raw_banner: Option<String>,
+}
+
+/// Multinomial Naive Bayes over banner tokens, trained alongside the
+/// numeric ensemble. The 29 `ServiceFeatures` digests discard the actual
+/// banner bytes (e.g. "SSH-2.0-OpenSSH", "220 mail ESMTP"), which carry
+/// strong signal that this model captures directly.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct NaiveBayesBanner {
+    // label -> token -> occurrence count
+    token_counts: HashMap<String, HashMap<String, u64>>,
+    // label -> total token occurrences across all its training banners
+    total_tokens: HashMap<String, u64>,
+    // label -> number of training banners seen
+    doc_counts: HashMap<String, u64>,
+    vocabulary: HashSet<String>,
+    total_docs: u64,
+}
+
+impl NaiveBayesBanner {
+    fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.to_lowercase())
+            .collect()
+    }
+
+    fn train_on(&mut self, banner: &str, label: &str) {
+        let tokens = Self::tokenize(banner);
+
+        *self.doc_counts.entry(label.to_string()).or_insert(0) += 1;
+        self.total_docs += 1;
+
+        let label_counts = self.token_counts.entry(label.to_string()).or_default();
+        let label_total = self.total_tokens.entry(label.to_string()).or_insert(0);
+        for token in tokens {
+            self.vocabulary.insert(token.clone());
+            *label_counts.entry(token).or_insert(0) += 1;
+            *label_total += 1;
+        }
+    }
+
+    /// Returns `(predicted_label, normalized_probability, top influential tokens)`,
+    /// or `None` if nothing has been trained yet.
+    fn predict(&self, banner: &str) -> Option<(String, f64, Vec<String>)> {
+        if self.total_docs == 0 {
+            return None;
+        }
+
+        let tokens = Self::tokenize(banner);
+        let vocab_size = self.vocabulary.len().max(1) as f64;
+
+        let mut log_scores: HashMap<String, f64> = HashMap::new();
+        let mut token_log_probs: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+
+        for label in self.doc_counts.keys() {
+            let prior = self.doc_counts[label] as f64 / self.total_docs as f64;
+            let label_total = *self.total_tokens.get(label).unwrap_or(&0) as f64;
+            let label_counts = self.token_counts.get(label);
+
+            let mut log_score = prior.ln();
+            let mut contributions = Vec::with_capacity(tokens.len());
+            for token in &tokens {
+                let count = label_counts
+                    .and_then(|counts| counts.get(token))
+                    .copied()
+                    .unwrap_or(0) as f64;
+                // Add-one Laplace smoothing over the full vocabulary.
+                let log_prob = ((count + 1.0) / (label_total + vocab_size)).ln();
+                log_score += log_prob;
+                contributions.push((token.clone(), log_prob));
+            }
+
+            log_scores.insert(label.clone(), log_score);
+            token_log_probs.insert(label.clone(), contributions);
+        }
+
+        let (best_label, &best_log_score) = log_scores
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())?;
+        let best_label = best_label.clone();
+
+        // Normalize the exponentiated log-scores into a probability,
+        // subtracting the max log-score first to keep `exp` well-behaved.
+        let max_log = log_scores.values().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let sum_exp: f64 = log_scores.values().map(|score| (score - max_log).exp()).sum();
+        let probability = if sum_exp > 0.0 {
+            (best_log_score - max_log).exp() / sum_exp
+        } else {
+            0.0
+        };
+
+        let mut top_tokens = token_log_probs.remove(&best_label).unwrap_or_default();
+        top_tokens.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        let influential = top_tokens.into_iter().take(3).map(|(token, _)| token).collect();
+
+        Some((best_label, probability, influential))
+    }
 }
 
 pub struct MLServiceClassifier {
@@ -74,7 +670,20 @@ pub struct MLServiceClassifier {
     svm_classifier: Option<SVC<f64, String>>,
     knn_classifier: Option<KNNClassifier<f64, String>>,
     decision_tree: Option<DecisionTreeClassifier<f64, String>>,
-    
+
+    // An externally-trained libSVM model, consulted alongside the smartcore
+    // ensemble above; independent of `svm_classifier`, which is trained
+    // in-process from `training_data`.
+    imported_svm: Option<ImportedSVM>,
+
+    // Token-level Naive Bayes over raw banner text, trained incrementally
+    // alongside the numeric ensemble.
+    naive_bayes_banner: NaiveBayesBanner,
+
+    // Gradient-boosted decision trees, one-vs-rest across service labels.
+    gbdt_config: GbdtConfig,
+    gbdt: Option<GbdtModel>,
+
     // Feature preprocessing
     scaler: Option<StandardScaler<f64>>,
     
@@ -85,10 +694,25 @@ pub struct MLServiceClassifier {
     // Model performance metrics
     model_accuracies: HashMap<String, f64>,
     feature_names: Vec<String>,
-    
+
+    // Global per-feature importance, derived from permutation importance
+    // over the trained random forest and decision tree (see
+    // `compute_feature_importances`), normalized to sum to 1.
+    feature_importances: HashMap<String, f64>,
+
+    // Per-model, per-predicted-class precision, estimated via stratified
+    // k-fold cross-validation in `train_models` (model name -> class name
+    // -> out-of-fold precision). Backs `calibrated_weight`.
+    model_class_precision: HashMap<String, HashMap<String, f64>>,
+
     // Confidence thresholds
     high_confidence_threshold: f64,
     medium_confidence_threshold: f64,
+
+    // Set once by `load_from_path`, so `add_training_example` doesn't
+    // silently clobber a deliberately-shipped pre-trained model with an
+    // automatic retrain after just a few live examples.
+    loaded_from_disk: bool,
 }
 
 impl Default for ServiceFeatures {
@@ -118,6 +742,11 @@ impl Default for ServiceFeatures {
             medium_response: 0.0,
             slow_response: 0.0,
             response_variance: 0.0,
+            spectral_bin_0: 0.0,
+            spectral_bin_1: 0.0,
+            spectral_bin_2: 0.0,
+            spectral_bin_3: 0.0,
+            spectral_centroid: 0.0,
         }
     }
 }
@@ -149,6 +778,11 @@ impl ServiceFeatures {
             self.medium_response,
             self.slow_response,
             self.response_variance,
+            self.spectral_bin_0,
+            self.spectral_bin_1,
+            self.spectral_bin_2,
+            self.spectral_bin_3,
+            self.spectral_centroid,
         ]
     }
     
@@ -178,8 +812,62 @@ impl ServiceFeatures {
             "medium_response".to_string(),
             "slow_response".to_string(),
             "response_variance".to_string(),
+            "spectral_bin_0".to_string(),
+            "spectral_bin_1".to_string(),
+            "spectral_bin_2".to_string(),
+            "spectral_bin_3".to_string(),
+            "spectral_centroid".to_string(),
         ]
     }
+
+    /// Populate the spectral timing fields from a fixed-length series of
+    /// repeated-probe response times. Entries beyond `SPECTRAL_PROBE_COUNT`
+    /// are ignored and a shorter series is zero-filled up to that length,
+    /// same as a timed-out (`None`) probe - both enter the series as a 0 ms
+    /// sample before it is de-meaned.
+    pub fn apply_spectral_timing(&mut self, probe_response_times_ms: &[Option<f64>]) {
+        let mut series: Vec<f64> = probe_response_times_ms
+            .iter()
+            .take(SPECTRAL_PROBE_COUNT)
+            .map(|sample| sample.unwrap_or(0.0))
+            .collect();
+        series.resize(SPECTRAL_PROBE_COUNT, 0.0);
+
+        let mean = series.iter().sum::<f64>() / series.len() as f64;
+        let mut spectrum: Vec<Complex<f64>> = series
+            .iter()
+            .map(|value| Complex::new(value - mean, 0.0))
+            .collect();
+
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(spectrum.len());
+        fft.process(&mut spectrum);
+
+        // A real-valued input's FFT output is symmetric, so only the first
+        // half carries independent information; bin 0 (DC) is skipped since
+        // de-meaning already zeroed it out.
+        let usable_bins = spectrum.len() / 2;
+        let magnitudes: Vec<f64> = spectrum[..usable_bins].iter().map(|bin| bin.norm()).collect();
+
+        let mut bins = [0.0; SPECTRAL_BIN_COUNT];
+        for (i, bin) in bins.iter_mut().enumerate() {
+            if let Some(magnitude) = magnitudes.get(i + 1) {
+                *bin = *magnitude;
+            }
+        }
+        self.spectral_bin_0 = bins[0];
+        self.spectral_bin_1 = bins[1];
+        self.spectral_bin_2 = bins[2];
+        self.spectral_bin_3 = bins[3];
+
+        let weighted_sum: f64 = magnitudes.iter().enumerate().map(|(i, m)| i as f64 * m).sum();
+        let magnitude_sum: f64 = magnitudes.iter().sum();
+        self.spectral_centroid = if magnitude_sum > 0.0 {
+            weighted_sum / magnitude_sum
+        } else {
+            0.0
+        };
+    }
 }
 
 impl MLServiceClassifier {
@@ -189,19 +877,123 @@ impl MLServiceClassifier {
             svm_classifier: None,
             knn_classifier: None,
             decision_tree: None,
+            imported_svm: None,
+            naive_bayes_banner: NaiveBayesBanner::default(),
+            gbdt_config: GbdtConfig::default(),
+            gbdt: None,
             scaler: None,
             training_data: Vec::new(),
             service_labels: Vec::new(),
             model_accuracies: HashMap::new(),
             feature_names: ServiceFeatures::feature_names(),
+            feature_importances: HashMap::new(),
+            model_class_precision: HashMap::new(),
             high_confidence_threshold: 0.8,
             medium_confidence_threshold: 0.5,
+            loaded_from_disk: false,
         }
     }
-    
+
+    /// Override the GBDT ensemble member's hyperparameters (depth,
+    /// leaf size, feature sampling ratio, learning rate, boosting
+    /// iterations). Takes effect on the next `train_models` call.
+    pub fn with_gbdt_config(mut self, config: GbdtConfig) -> Self {
+        self.gbdt_config = config;
+        self
+    }
+
+    /// Serialize the fitted models, scaler, and derived metadata to `path`
+    /// as a single versioned JSON file. Training examples are intentionally
+    /// excluded - only what `classify_service` actually needs is persisted.
+    pub fn save_to_path(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = ClassifierSnapshotRef {
+            format_version: CLASSIFIER_SNAPSHOT_VERSION,
+            random_forest: &self.random_forest,
+            svm_classifier: &self.svm_classifier,
+            knn_classifier: &self.knn_classifier,
+            decision_tree: &self.decision_tree,
+            scaler: &self.scaler,
+            model_accuracies: &self.model_accuracies,
+            service_labels: &self.service_labels,
+            feature_names: &self.feature_names,
+            imported_svm: &self.imported_svm,
+            naive_bayes_banner: &self.naive_bayes_banner,
+            feature_importances: &self.feature_importances,
+            gbdt_config: &self.gbdt_config,
+            gbdt: &self.gbdt,
+            model_class_precision: &self.model_class_precision,
+        };
+
+        let json = serde_json::to_string(&snapshot)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a classifier snapshot written by `save_to_path`, ready to
+    /// classify immediately without any local training.
+    pub fn load_from_path(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let snapshot: ClassifierSnapshot = serde_json::from_str(&content)?;
+
+        if snapshot.format_version != CLASSIFIER_SNAPSHOT_VERSION {
+            return Err(format!(
+                "unsupported classifier snapshot version {} (expected {})",
+                snapshot.format_version, CLASSIFIER_SNAPSHOT_VERSION
+            )
+            .into());
+        }
+
+        Ok(Self {
+            random_forest: snapshot.random_forest,
+            svm_classifier: snapshot.svm_classifier,
+            knn_classifier: snapshot.knn_classifier,
+            decision_tree: snapshot.decision_tree,
+            imported_svm: snapshot.imported_svm,
+            naive_bayes_banner: snapshot.naive_bayes_banner,
+            feature_importances: snapshot.feature_importances,
+            gbdt_config: snapshot.gbdt_config,
+            gbdt: snapshot.gbdt,
+            model_class_precision: snapshot.model_class_precision,
+            scaler: snapshot.scaler,
+            training_data: Vec::new(),
+            service_labels: snapshot.service_labels,
+            model_accuracies: snapshot.model_accuracies,
+            feature_names: snapshot.feature_names,
+            high_confidence_threshold: 0.8,
+            medium_confidence_threshold: 0.5,
+            loaded_from_disk: true,
+        })
+    }
+
+    /// Import a libSVM `svm-train .model` file for use alongside the
+    /// in-process ensemble. `label_names` maps libSVM's bare integer class
+    /// labels (the only label form the file format carries) back to mlscan
+    /// service names; an unmapped label falls back to `svm_class_<n>`.
+    pub fn load_libsvm_model(
+        &mut self,
+        path: &Path,
+        label_names: HashMap<i32, String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut svm = parse_libsvm_model(&content)?;
+        svm.label_names = label_names;
+        self.imported_svm = Some(svm);
+        Ok(())
+    }
+
     pub fn add_training_example(&mut self, example: TrainingExample) {
+        if let Some(banner) = &example.raw_banner {
+            self.naive_bayes_banner.train_on(banner, &example.service_label);
+        }
+
         self.training_data.push(example);
-        
+
+        // A loaded, pre-trained model is a deliberate operator choice - don't
+        // let a handful of freshly observed examples silently retrain over it.
+        if self.loaded_from_disk {
+            return;
+        }
+
         // Retrain models periodically
         if self.training_data.len() % 50 == 0 && self.training_data.len() >= 100 {
             println!("🧠 Retraining ML models with {} examples", self.training_data.len());
@@ -282,7 +1074,38 @@ impl MLServiceClassifier {
         let dt_accuracy = accuracy(&y_test, &dt_predictions);
         self.model_accuracies.insert("decision_tree".to_string(), dt_accuracy);
         self.decision_tree = Some(dt);
-        
+
+        // GBDT boosts one-vs-rest across all classes, so it needs enough
+        // data per class to be worth the extra training time - same
+        // threshold as the SVM above.
+        if self.training_data.len() >= 50 {
+            println!("🚀 Training GBDT ensemble member...");
+            let mut indices: Vec<usize> = (0..features_matrix.len()).collect();
+            indices.shuffle(&mut rand::thread_rng());
+            let split_at = ((features_matrix.len() as f64) * 0.8) as usize;
+            let (train_idx, test_idx) = indices.split_at(split_at);
+
+            let gbdt_train_features: Vec<Vec<f64>> =
+                train_idx.iter().map(|&i| features_matrix[i].clone()).collect();
+            let gbdt_train_labels: Vec<String> = train_idx.iter().map(|&i| labels[i].clone()).collect();
+            let gbdt_test_features: Vec<Vec<f64>> =
+                test_idx.iter().map(|&i| features_matrix[i].clone()).collect();
+            let gbdt_test_labels: Vec<String> = test_idx.iter().map(|&i| labels[i].clone()).collect();
+
+            match GbdtModel::train(&self.gbdt_config, &gbdt_train_features, &gbdt_train_labels) {
+                Ok(gbdt) => {
+                    let gbdt_predictions = gbdt.predict(&gbdt_test_features);
+                    let gbdt_accuracy = accuracy(&gbdt_test_labels, &gbdt_predictions);
+                    self.model_accuracies.insert("gbdt".to_string(), gbdt_accuracy);
+                    println!("   GBDT accuracy: {:.2}%", gbdt_accuracy * 100.0);
+                    self.gbdt = Some(gbdt);
+                }
+                Err(e) => {
+                    eprintln!("⚠️ GBDT training failed: {}", e);
+                }
+            }
+        }
+
         println!("✅ ML model training complete!");
         println!("   Random Forest accuracy: {:.2}%", rf_accuracy * 100.0);
         if let Some(svm_acc) = self.model_accuracies.get("svm") {
@@ -290,122 +1113,415 @@ impl MLServiceClassifier {
         }
         println!("   KNN accuracy: {:.2}%", knn_accuracy * 100.0);
         println!("   Decision Tree accuracy: {:.2}%", dt_accuracy * 100.0);
-        
+
+        // Per-model global accuracy (above) says how good a model is on
+        // average, but not how much to trust it for the *specific* class it
+        // just predicted - a model can be 90% accurate overall while being
+        // unreliable on one rare label. Cross-validate each model's
+        // out-of-fold predictions and keep per-class precision instead, so
+        // `calibrated_posterior` can weight a vote by how trustworthy this
+        // model actually is for the class it voted for.
+        println!("📐 Cross-validating per-class precision...");
+
+        self.model_class_precision.insert(
+            "random_forest".to_string(),
+            Self::cross_validated_class_precision(&features_matrix, &labels, 5, |train_f, train_l, test_f| {
+                let x_train = DenseMatrix::from_2d_vec(&train_f.to_vec());
+                let x_test = DenseMatrix::from_2d_vec(&test_f.to_vec());
+                match RandomForestClassifier::fit(&x_train, &train_l.to_vec(), Default::default()) {
+                    Ok(model) => model
+                        .predict(&x_test)
+                        .unwrap_or_else(|_| vec![String::new(); test_f.len()]),
+                    Err(_) => vec![String::new(); test_f.len()],
+                }
+            }),
+        );
+
+        if self.training_data.len() >= 50 {
+            self.model_class_precision.insert(
+                "svm".to_string(),
+                Self::cross_validated_class_precision(&features_matrix, &labels, 5, |train_f, train_l, test_f| {
+                    let x_train = DenseMatrix::from_2d_vec(&train_f.to_vec());
+                    let x_test = DenseMatrix::from_2d_vec(&test_f.to_vec());
+                    match SVC::fit(&x_train, &train_l.to_vec(), Default::default()) {
+                        Ok(model) => model
+                            .predict(&x_test)
+                            .unwrap_or_else(|_| vec![String::new(); test_f.len()]),
+                        Err(_) => vec![String::new(); test_f.len()],
+                    }
+                }),
+            );
+        }
+
+        self.model_class_precision.insert(
+            "knn".to_string(),
+            Self::cross_validated_class_precision(&features_matrix, &labels, 5, |train_f, train_l, test_f| {
+                let x_train = DenseMatrix::from_2d_vec(&train_f.to_vec());
+                let x_test = DenseMatrix::from_2d_vec(&test_f.to_vec());
+                match KNNClassifier::fit(&x_train, &train_l.to_vec(), Default::default()) {
+                    Ok(model) => model
+                        .predict(&x_test)
+                        .unwrap_or_else(|_| vec![String::new(); test_f.len()]),
+                    Err(_) => vec![String::new(); test_f.len()],
+                }
+            }),
+        );
+
+        self.model_class_precision.insert(
+            "decision_tree".to_string(),
+            Self::cross_validated_class_precision(&features_matrix, &labels, 5, |train_f, train_l, test_f| {
+                let x_train = DenseMatrix::from_2d_vec(&train_f.to_vec());
+                let x_test = DenseMatrix::from_2d_vec(&test_f.to_vec());
+                match DecisionTreeClassifier::fit(&x_train, &train_l.to_vec(), Default::default()) {
+                    Ok(model) => model
+                        .predict(&x_test)
+                        .unwrap_or_else(|_| vec![String::new(); test_f.len()]),
+                    Err(_) => vec![String::new(); test_f.len()],
+                }
+            }),
+        );
+
+        if self.training_data.len() >= 50 {
+            let gbdt_config = self.gbdt_config.clone();
+            self.model_class_precision.insert(
+                "gbdt".to_string(),
+                Self::cross_validated_class_precision(&features_matrix, &labels, 5, |train_f, train_l, test_f| {
+                    match GbdtModel::train(&gbdt_config, train_f, train_l) {
+                        Ok(model) => model.predict(test_f),
+                        Err(_) => vec![String::new(); test_f.len()],
+                    }
+                }),
+            );
+        }
+
+        self.compute_feature_importances(&features_matrix, &labels);
+
         Ok(())
     }
-    
-    pub fn classify_service(&self, features: &ServiceFeatures) -> ServiceClassification {
-        let mut confidence_scores = HashMap::new();
+
+    /// Derive global per-feature importance from the trained random forest
+    /// and decision tree via permutation importance: reshuffle one feature
+    /// column at a time and measure how much the model's accuracy drops.
+    ///
+    /// This is a deviation from walking each tree's split nodes and summing
+    /// the weighted Gini-impurity decrease, which is what per-feature
+    /// importance usually means: smartcore's `RandomForestClassifier`/
+    /// `DecisionTreeClassifier` don't expose their internal split nodes
+    /// publicly, so that decrease isn't reachable from outside the crate.
+    /// Permutation importance only needs the `predict` surface already used
+    /// above, but it answers a related, not identical, question (how much
+    /// accuracy drops when a feature is shuffled, rather than how much of
+    /// the tree's decisions it drove) and costs one re-prediction pass per
+    /// feature. Flagging this here rather than calling it equivalent.
+    fn compute_feature_importances(&mut self, features_matrix: &[Vec<f64>], labels: &[String]) {
+        let cols = self.feature_names.len();
+        let mut raw = vec![0.0f64; cols];
+        let mut contributors = 0u32;
+
+        if let Some(rf) = &self.random_forest {
+            if let Some(contribution) =
+                Self::permutation_importance(|m| rf.predict(m).ok(), features_matrix, labels, cols)
+            {
+                for (acc, value) in raw.iter_mut().zip(contribution.iter()) {
+                    *acc += value;
+                }
+                contributors += 1;
+            }
+        }
+
+        if let Some(dt) = &self.decision_tree {
+            if let Some(contribution) =
+                Self::permutation_importance(|m| dt.predict(m).ok(), features_matrix, labels, cols)
+            {
+                for (acc, value) in raw.iter_mut().zip(contribution.iter()) {
+                    *acc += value;
+                }
+                contributors += 1;
+            }
+        }
+
+        if contributors == 0 {
+            return;
+        }
+
+        let total: f64 = raw.iter().sum();
+        let mut importances = HashMap::new();
+        for (idx, name) in self.feature_names.iter().enumerate() {
+            let value = if total > 0.0 { raw[idx] / total } else { 0.0 };
+            importances.insert(name.clone(), value);
+        }
+        self.feature_importances = importances;
+    }
+
+    /// Permutation importance for a single model: the accuracy drop caused
+    /// by independently shuffling each feature column, one at a time.
+    fn permutation_importance(
+        predict: impl Fn(&DenseMatrix<f64>) -> Option<Vec<String>>,
+        features_matrix: &[Vec<f64>],
+        labels: &[String],
+        cols: usize,
+    ) -> Option<Vec<f64>> {
+        let labels = labels.to_vec();
+        let baseline_matrix = DenseMatrix::from_2d_vec(&features_matrix.to_vec());
+        let baseline_predictions = predict(&baseline_matrix)?;
+        let baseline_accuracy = accuracy(&labels, &baseline_predictions);
+
+        let mut data: Vec<Vec<f64>> = features_matrix.to_vec();
+        let mut importances = vec![0.0; cols];
+
+        for feature_idx in 0..cols {
+            let original: Vec<f64> = data.iter().map(|row| row[feature_idx]).collect();
+            // A random shuffle decorrelates the column from its row.
+            let mut shuffled = original.clone();
+            shuffled.shuffle(&mut rand::thread_rng());
+            for (row, value) in data.iter_mut().zip(shuffled.iter()) {
+                row[feature_idx] = *value;
+            }
+
+            if let Some(permuted_predictions) = predict(&DenseMatrix::from_2d_vec(&data)) {
+                let permuted_accuracy = accuracy(&labels, &permuted_predictions);
+                importances[feature_idx] = (baseline_accuracy - permuted_accuracy).max(0.0);
+            }
+
+            for (row, value) in data.iter_mut().zip(original.iter()) {
+                row[feature_idx] = *value;
+            }
+        }
+
+        Some(importances)
+    }
+
+    /// Assign each row to one of `k` folds, keeping each class's rows
+    /// spread as evenly across folds as possible (stratification).
+    fn stratified_folds(labels: &[String], k: usize) -> Vec<usize> {
+        let mut by_label: HashMap<&String, Vec<usize>> = HashMap::new();
+        for (i, label) in labels.iter().enumerate() {
+            by_label.entry(label).or_default().push(i);
+        }
+
+        let mut fold_of = vec![0usize; labels.len()];
+        for indices in by_label.into_values() {
+            for (j, idx) in indices.into_iter().enumerate() {
+                fold_of[idx] = j % k;
+            }
+        }
+        fold_of
+    }
+
+    /// Out-of-fold precision per predicted class: of the rows where a model
+    /// predicted class `c`, the fraction whose true label was actually `c`.
+    fn class_precision_from_predictions(predictions: &[String], true_labels: &[String]) -> HashMap<String, f64> {
+        let mut correct: HashMap<String, u32> = HashMap::new();
+        let mut total: HashMap<String, u32> = HashMap::new();
+
+        for (pred, truth) in predictions.iter().zip(true_labels.iter()) {
+            if pred.is_empty() {
+                continue; // row fell in a degenerate fold and was never predicted
+            }
+            *total.entry(pred.clone()).or_insert(0) += 1;
+            if pred == truth {
+                *correct.entry(pred.clone()).or_insert(0) += 1;
+            }
+        }
+
+        total
+            .into_iter()
+            .map(|(class, t)| {
+                let c = *correct.get(&class).unwrap_or(&0) as f64;
+                (class, if t > 0 { c / t as f64 } else { 0.5 })
+            })
+            .collect()
+    }
+
+    /// Run stratified `k`-fold cross-validation, calling `fit_predict` once
+    /// per fold with that fold's train/test split, and turn the pooled
+    /// out-of-fold predictions into a per-class precision map.
+    fn cross_validated_class_precision(
+        features_matrix: &[Vec<f64>],
+        labels: &[String],
+        k: usize,
+        fit_predict: impl Fn(&[Vec<f64>], &[String], &[Vec<f64>]) -> Vec<String>,
+    ) -> HashMap<String, f64> {
+        let n = features_matrix.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+        let k = k.min(n).max(2);
+        let fold_of = Self::stratified_folds(labels, k);
+
+        let mut oof_predictions = vec![String::new(); n];
+        for fold in 0..k {
+            let train_idx: Vec<usize> = (0..n).filter(|&i| fold_of[i] != fold).collect();
+            let test_idx: Vec<usize> = (0..n).filter(|&i| fold_of[i] == fold).collect();
+            if train_idx.is_empty() || test_idx.is_empty() {
+                continue;
+            }
+
+            let train_features: Vec<Vec<f64>> = train_idx.iter().map(|&i| features_matrix[i].clone()).collect();
+            let train_labels: Vec<String> = train_idx.iter().map(|&i| labels[i].clone()).collect();
+            let test_features: Vec<Vec<f64>> = test_idx.iter().map(|&i| features_matrix[i].clone()).collect();
+
+            let fold_predictions = fit_predict(&train_features, &train_labels, &test_features);
+            for (idx, pred) in test_idx.iter().zip(fold_predictions.into_iter()) {
+                oof_predictions[*idx] = pred;
+            }
+        }
+
+        Self::class_precision_from_predictions(&oof_predictions, labels)
+    }
+
+    pub fn classify_service(
+        &self,
+        features: &ServiceFeatures,
+        raw_banner: Option<&str>,
+    ) -> ServiceClassification {
         let mut predictions = Vec::new();
         let mut reasoning = Vec::new();
-        
+
         let feature_vector = vec![features.to_vector()];
         let feature_matrix = DenseMatrix::from_2d_vec(&feature_vector);
-        
+
         // Get predictions from each trained model
         if let Some(ref rf) = self.random_forest {
             if let Ok(prediction) = rf.predict(&feature_matrix) {
                 if let Some(pred) = prediction.first() {
                     predictions.push((pred.clone(), "random_forest"));
-                    confidence_scores.insert("random_forest".to_string(), 
-                        self.model_accuracies.get("random_forest").unwrap_or(&0.5).clone());
                     reasoning.push(format!("Random Forest predicts: {}", pred));
                 }
             }
         }
-        
+
         if let Some(ref svm) = self.svm_classifier {
             if let Ok(prediction) = svm.predict(&feature_matrix) {
                 if let Some(pred) = prediction.first() {
                     predictions.push((pred.clone(), "svm"));
-                    confidence_scores.insert("svm".to_string(), 
-                        self.model_accuracies.get("svm").unwrap_or(&0.5).clone());
                     reasoning.push(format!("SVM predicts: {}", pred));
                 }
             }
         }
-        
+
         if let Some(ref knn) = self.knn_classifier {
             if let Ok(prediction) = knn.predict(&feature_matrix) {
                 if let Some(pred) = prediction.first() {
                     predictions.push((pred.clone(), "knn"));
-                    confidence_scores.insert("knn".to_string(), 
-                        self.model_accuracies.get("knn").unwrap_or(&0.5).clone());
                     reasoning.push(format!("KNN predicts: {}", pred));
                 }
             }
         }
-        
+
         if let Some(ref dt) = self.decision_tree {
             if let Ok(prediction) = dt.predict(&feature_matrix) {
                 if let Some(pred) = prediction.first() {
                     predictions.push((pred.clone(), "decision_tree"));
-                    confidence_scores.insert("decision_tree".to_string(), 
-                        self.model_accuracies.get("decision_tree").unwrap_or(&0.5).clone());
                     reasoning.push(format!("Decision Tree predicts: {}", pred));
                 }
             }
         }
-        
-        // Ensemble voting - weighted by model accuracy
-        let final_prediction = self.ensemble_vote(&predictions);
-        let ensemble_confidence = self.calculate_ensemble_confidence(&predictions, &final_prediction);
-        
+
+        if let Some(ref gbdt) = self.gbdt {
+            if let Some(pred) = gbdt.predict_one(&features.to_vector()) {
+                predictions.push((pred.clone(), "gbdt"));
+                reasoning.push(format!("GBDT predicts: {}", pred));
+            }
+        }
+
+        if let Some(ref svm) = self.imported_svm {
+            let pred = svm.predict_value(&features.to_vector());
+            predictions.push((pred.clone(), "imported_svm"));
+            reasoning.push(format!("Imported libSVM model predicts: {}", pred));
+        }
+
+        if let Some(banner) = raw_banner {
+            if let Some((pred, _probability, top_tokens)) = self.naive_bayes_banner.predict(banner) {
+                predictions.push((pred.clone(), "naive_bayes_banner"));
+                if top_tokens.is_empty() {
+                    reasoning.push(format!("Naive Bayes banner model predicts: {}", pred));
+                } else {
+                    reasoning.push(format!(
+                        "Naive Bayes banner model predicts: {} (influential tokens: {})",
+                        pred,
+                        top_tokens.join(", ")
+                    ));
+                }
+            }
+        }
+
+        // Combine into a calibrated posterior over candidate services, using
+        // each model's cross-validated precision for the class it actually
+        // predicted (not a single flat per-model accuracy), so confidence
+        // varies with which class a given sample lands on.
+        let (final_prediction, confidence, confidence_scores) =
+            self.calibrated_posterior(&predictions);
+
         // Generate feature importance explanation
         let feature_importance = self.analyze_feature_importance(features);
-        
+
         ServiceClassification {
             service_name: final_prediction,
-            confidence: ensemble_confidence,
+            confidence,
             confidence_scores,
             feature_importance,
             reasoning,
         }
     }
-    
-    fn ensemble_vote(&self, predictions: &[(String, &str)]) -> String {
-        let mut vote_weights = HashMap::new();
-        
-        for (prediction, model_name) in predictions {
-            let weight = self.model_accuracies.get(*model_name).unwrap_or(&0.5);
-            *vote_weights.entry(prediction.clone()).or_insert(0.0) += weight;
-        }
-        
-        vote_weights.into_iter()
-            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
-            .map(|(service, _)| service)
-            .unwrap_or_else(|| "unknown".to_string())
+
+    /// Weight a model predicting `class` by that model's out-of-fold
+    /// precision for `class` specifically (from `model_class_precision`,
+    /// populated by cross-validation in `train_models`), falling back to
+    /// the model's flat held-out accuracy, and then 0.5 if neither exists
+    /// (e.g. the model hasn't been trained on enough data yet).
+    fn calibrated_weight(&self, model_name: &str, class: &str) -> f64 {
+        self.model_class_precision
+            .get(model_name)
+            .and_then(|per_class| per_class.get(class))
+            .copied()
+            .unwrap_or_else(|| *self.model_accuracies.get(model_name).unwrap_or(&0.5))
     }
-    
-    fn calculate_ensemble_confidence(&self, predictions: &[(String, &str)], final_prediction: &str) -> f64 {
-        let total_weight: f64 = predictions.iter()
-            .map(|(_, model)| self.model_accuracies.get(*model).unwrap_or(&0.5))
-            .sum();
-            
-        let supporting_weight: f64 = predictions.iter()
-            .filter(|(pred, _)| pred == final_prediction)
-            .map(|(_, model)| self.model_accuracies.get(*model).unwrap_or(&0.5))
-            .sum();
-            
-        if total_weight > 0.0 {
-            supporting_weight / total_weight
+
+    /// Combine per-model predictions into a true posterior over candidate
+    /// services: each vote is weighted by that model's calibrated,
+    /// per-class reliability rather than one flat accuracy number, and the
+    /// weighted votes are normalized into probabilities that sum to 1.
+    /// Returns `(winning_class, winning_probability, per_class_distribution)`.
+    fn calibrated_posterior(&self, predictions: &[(String, &str)]) -> (String, f64, HashMap<String, f64>) {
+        let mut raw_scores: HashMap<String, f64> = HashMap::new();
+        for (class, model_name) in predictions {
+            *raw_scores.entry(class.clone()).or_insert(0.0) += self.calibrated_weight(model_name, class);
+        }
+
+        let total: f64 = raw_scores.values().sum();
+        let posterior: HashMap<String, f64> = if total > 0.0 {
+            raw_scores.iter().map(|(class, score)| (class.clone(), score / total)).collect()
         } else {
-            0.0
+            raw_scores
+        };
+
+        match posterior
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(class, probability)| (class.clone(), *probability))
+        {
+            Some((class, probability)) => (class, probability, posterior),
+            None => ("unknown".to_string(), 0.0, posterior),
         }
     }
-    
+
     fn analyze_feature_importance(&self, features: &ServiceFeatures) -> HashMap<String, f64> {
         let mut importance = HashMap::new();
         let feature_values = features.to_vector();
-        
-        // Simple heuristic-based feature importance
+
+        // Scale each feature's model-derived global importance (permutation
+        // importance from `compute_feature_importances`) by how strongly
+        // this particular sample exercises that feature, so the result is
+        // grounded in what the models actually learned rather than just
+        // echoing the raw value.
         for (i, &value) in feature_values.iter().enumerate() {
             if let Some(feature_name) = self.feature_names.get(i) {
-                // Higher values generally indicate more importance for binary features
-                importance.insert(feature_name.clone(), value.abs());
+                let global_importance = self.feature_importances.get(feature_name).copied().unwrap_or(0.0);
+                importance.insert(feature_name.clone(), global_importance * value.abs());
             }
         }
-        
+
         importance
     }
     
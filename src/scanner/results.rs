@@ -0,0 +1,73 @@
+//! Result types shared across the scan engine, output formatters, and
+//! plugins: per-port outcome, per-host summary, and the top-level
+//! multi-host report handed to `OutputWriter`.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::ScanType;
+
+pub use crate::plugins::ServiceInfo;
+
+/// Outcome of probing a single port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PortStatus {
+    Open,
+    Closed,
+    Filtered,
+}
+
+impl fmt::Display for PortStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortStatus::Open => write!(f, "open"),
+            PortStatus::Closed => write!(f, "closed"),
+            PortStatus::Filtered => write!(f, "filtered"),
+        }
+    }
+}
+
+/// Result of probing a single port on a single host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortResult {
+    pub port: u16,
+    pub status: PortStatus,
+    pub is_filtered: bool,
+    pub response_time: Option<f64>,
+    pub service_detected: Option<ServiceInfo>,
+    // Kernel-reported retransmit count for this connection, when available
+    // (`Connect` scans on Linux, via `TCP_INFO`). A non-zero count is a
+    // sign the wire path is lossy even when the port ultimately answered.
+    pub retransmits: Option<u32>,
+}
+
+/// All port results for a single scanned host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub target: String,
+    pub target_ip: IpAddr,
+    pub scan_type: ScanType,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub ports: Vec<PortResult>,
+}
+
+/// Top-level report for one `Scanner::scan` invocation, covering every host
+/// expanded from the target spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiHostScanResult {
+    pub target_spec: String,
+    pub scan_type: ScanType,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub total_hosts: usize,
+    pub total_ports: usize,
+    pub hosts: Vec<ScanResult>,
+    // Only set when `ScanOrder::Random` shuffled the port list, so a run
+    // that looked interesting can be replayed with the same probe order via
+    // `--scan-order random --scan-seed <this value>`.
+    pub scan_order_seed: Option<u64>,
+}
@@ -0,0 +1,290 @@
+//! Best-effort service identification for open ports.
+//!
+//! Most services still announce themselves with a plaintext banner
+//! (`SSH-2.0-...`, an SMTP `220` line, ...), so [`ServiceDetector`] tries that
+//! first. Encrypted services don't, so on the ports that are almost always
+//! TLS - or when the plaintext banner read times out instead of ever
+//! producing bytes - it completes a real TLS handshake instead and reports
+//! the negotiated protocol version, ALPN protocol, and the leaf
+//! certificate's subject/SANs/validity. Accepting whatever certificate the
+//! target presents (see [`AcceptAnyCert`]) is deliberate: we're scanning an
+//! arbitrary, often-internal host to see what it presents, not validating
+//! that presentation against a trust store.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_rustls::TlsConnector;
+
+use crate::plugins::ServiceInfo;
+use super::ml_classifier::{MLServiceClassifier, ServiceFeatures, TrainingExample};
+
+/// How long to wait for a plaintext banner, and separately for each step of
+/// a TLS handshake, before giving up on this port.
+const PROBE_TIMEOUT_MS: u64 = 1500;
+
+/// Max bytes read when grabbing a plaintext banner - enough for the first
+/// line or two that identifies the service without reading an attacker- or
+/// server-controlled stream indefinitely.
+const BANNER_READ_LEN: usize = 512;
+
+/// Ports that are practically always TLS, so a TLS handshake is attempted
+/// up front instead of only as a fallback after the plaintext banner read
+/// times out - trying plaintext first here would just burn the whole
+/// banner timeout for nothing.
+const COMMON_TLS_PORTS: &[u16] = &[443, 465, 636, 993, 995, 8443];
+
+pub struct ServiceDetector {
+    // Weakly-supervised from the heuristic detections below (there's no
+    // ground-truth oracle at scan time) so that, over a long-running scan,
+    // the ensemble's own prediction becomes a second, increasingly-trained
+    // opinion folded into `additional_info` alongside the heuristic's.
+    ml_classifier: Mutex<MLServiceClassifier>,
+}
+
+impl ServiceDetector {
+    pub fn new() -> Self {
+        Self {
+            ml_classifier: Mutex::new(MLServiceClassifier::new()),
+        }
+    }
+
+    /// Identify whatever is listening on `target:port`. `hostname` is the
+    /// original spec token the target resolved from (a DNS name, or just the
+    /// IP again for a literal/CIDR/range target) and is sent as the TLS SNI
+    /// when it's an actual hostname. Returns `None` if neither a plaintext
+    /// banner nor a TLS handshake produced anything - the port is open, but
+    /// silently so (e.g. it expects us to speak first).
+    pub async fn detect_service(&self, target: IpAddr, hostname: Option<&str>, port: u16) -> Option<ServiceInfo> {
+        let mut info = if COMMON_TLS_PORTS.contains(&port) {
+            probe_tls(target, hostname, port).await?
+        } else {
+            match probe_banner(target, port).await {
+                Some(info) => info,
+                None => probe_tls(target, hostname, port).await?,
+            }
+        };
+
+        self.refine_with_ml(target, port, &mut info);
+        Some(info)
+    }
+
+    /// Turn the heuristic detection's own banner/confidence into
+    /// `ServiceFeatures`, feed it to the ensemble as a training example
+    /// labeled with the heuristic's own verdict, and - once the ensemble has
+    /// seen enough examples to be ready - fold its independent prediction
+    /// and confidence into `additional_info` alongside the heuristic's.
+    fn refine_with_ml(&self, target: IpAddr, port: u16, info: &mut ServiceInfo) {
+        let banner = info.banner.as_deref().unwrap_or_default();
+
+        let mut features = ServiceFeatures::default();
+        features.response_length = banner.len() as f64;
+        features.has_ascii_banner = if !banner.is_empty() && banner.is_ascii() { 1.0 } else { 0.0 };
+        features.has_http_headers = if banner.to_uppercase().starts_with("HTTP/") { 1.0 } else { 0.0 };
+        features.starts_with_greeting = if banner.starts_with("SSH-") || banner.starts_with("220") { 1.0 } else { 0.0 };
+        features.contains_version_string = if banner.chars().any(|c| c.is_ascii_digit()) { 1.0 } else { 0.0 };
+        features.connection_accepted = 1.0;
+
+        let mut classifier = self.ml_classifier.lock().unwrap();
+
+        if classifier.is_ready() {
+            let classification = classifier.classify_service(&features, info.banner.as_deref());
+            info.additional_info.insert("ml_service".to_string(), classification.service_name);
+            info.additional_info.insert("ml_confidence".to_string(), format!("{:.2}", classification.confidence));
+        }
+
+        classifier.add_training_example(TrainingExample {
+            features,
+            service_label: info.name.clone(),
+            target,
+            port,
+            timestamp: unix_timestamp(),
+            raw_banner: info.banner.clone(),
+        });
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Default for ServiceDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn probe_banner(target: IpAddr, port: u16) -> Option<ServiceInfo> {
+    let probe_timeout = Duration::from_millis(PROBE_TIMEOUT_MS);
+
+    let mut stream = timeout(probe_timeout, TcpStream::connect((target, port)))
+        .await
+        .ok()?
+        .ok()?;
+
+    let mut buf = vec![0u8; BANNER_READ_LEN];
+    let n = timeout(probe_timeout, stream.read(&mut buf)).await.ok()?.ok()?;
+    if n == 0 {
+        return None;
+    }
+
+    let banner = String::from_utf8_lossy(&buf[..n]).trim().to_string();
+    if banner.is_empty() {
+        return None;
+    }
+
+    Some(ServiceInfo {
+        name: guess_plaintext_service(port, &banner),
+        version: banner.split_whitespace().nth(1).map(|s| s.to_string()),
+        banner: Some(banner),
+        confidence: 0.6,
+        additional_info: HashMap::new(),
+    })
+}
+
+async fn probe_tls(target: IpAddr, hostname: Option<&str>, port: u16) -> Option<ServiceInfo> {
+    let probe_timeout = Duration::from_millis(PROBE_TIMEOUT_MS);
+
+    let tcp_stream = timeout(probe_timeout, TcpStream::connect((target, port)))
+        .await
+        .ok()?
+        .ok()?;
+
+    let mut tls_config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyCert))
+        .with_no_client_auth();
+    tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let connector = TlsConnector::from(Arc::new(tls_config));
+
+    // Send SNI equal to the resolved hostname when the caller has one, so a
+    // name-based vhost behind the target IP presents its real certificate
+    // instead of whatever it serves by default; fall back to the bare IP
+    // for literal IP/CIDR/range targets, which have no separate hostname.
+    let ip_string = target.to_string();
+    let sni = hostname.filter(|h| **h != ip_string).unwrap_or(&ip_string);
+    let server_name = rustls::ServerName::try_from(sni).ok()?;
+
+    let tls_stream = timeout(probe_timeout, connector.connect(server_name, tcp_stream))
+        .await
+        .ok()?
+        .ok()?;
+
+    let (_, conn) = tls_stream.get_ref();
+    let mut additional_info = HashMap::new();
+
+    let alpn = conn
+        .alpn_protocol()
+        .map(|p| String::from_utf8_lossy(p).to_string());
+    if let Some(alpn) = &alpn {
+        additional_info.insert("alpn".to_string(), alpn.clone());
+    }
+
+    let version = conn.protocol_version().map(|v| format!("{:?}", v));
+    let mut banner = version.clone().unwrap_or_default();
+    let mut service_name = "tls".to_string();
+
+    if let Some(certs) = conn.peer_certificates() {
+        if let Some(leaf) = certs.first() {
+            if let Ok((_, parsed)) = x509_parser::parse_x509_certificate(leaf.as_ref()) {
+                let subject = parsed.subject().to_string();
+                additional_info.insert("subject".to_string(), subject.clone());
+                additional_info.insert("issuer".to_string(), parsed.issuer().to_string());
+                additional_info.insert(
+                    "not_before".to_string(),
+                    parsed.validity().not_before.to_string(),
+                );
+                additional_info.insert(
+                    "not_after".to_string(),
+                    parsed.validity().not_after.to_string(),
+                );
+
+                let sans: Vec<String> = parsed
+                    .extensions()
+                    .iter()
+                    .find_map(|ext| match ext.parsed_extension() {
+                        x509_parser::extensions::ParsedExtension::SubjectAlternativeName(san) => {
+                            Some(san.general_names.iter().map(|gn| gn.to_string()).collect())
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                if !sans.is_empty() {
+                    additional_info.insert("sans".to_string(), sans.join(","));
+                }
+
+                banner = format!("{} subject={}", banner, subject);
+                service_name = service_name_for_tls_port(port);
+            }
+        }
+    }
+
+    Some(ServiceInfo {
+        name: service_name,
+        version,
+        banner: Some(banner),
+        confidence: 0.9,
+        additional_info,
+    })
+}
+
+/// `ServerCertVerifier` that accepts any certificate presented - see the
+/// module doc comment for why that's the right call here.
+struct AcceptAnyCert;
+
+impl rustls::client::ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+fn service_name_for_tls_port(port: u16) -> String {
+    match port {
+        443 | 8443 => "https",
+        993 => "imaps",
+        995 => "pop3s",
+        465 => "smtps",
+        636 => "ldaps",
+        _ => "tls",
+    }
+    .to_string()
+}
+
+fn guess_plaintext_service(port: u16, banner: &str) -> String {
+    if banner.starts_with("SSH-") {
+        return "ssh".to_string();
+    }
+    if banner.starts_with("220") && banner.to_uppercase().contains("FTP") {
+        return "ftp".to_string();
+    }
+    if banner.starts_with("220") {
+        return "smtp".to_string();
+    }
+
+    match port {
+        21 => "ftp",
+        22 => "ssh",
+        25 => "smtp",
+        80 => "http",
+        110 => "pop3",
+        143 => "imap",
+        _ => "unknown",
+    }
+    .to_string()
+}
@@ -0,0 +1,458 @@
+//! TCP scanning primitives.
+//!
+//! `connect_scan`/`fast_connect_scan` are plain socket connects and need no
+//! special privileges; they're what `ScanType::Connect` uses, and the only
+//! variants that can be routed through a proxy. `syn_scan`/`fin_scan`/
+//! `xmas_scan`/`null_scan` hand-roll a bare IPv4 TCP segment over a raw
+//! socket and classify whatever comes back - the same "construct the wire
+//! format by hand" approach the QUIC probe in `plugins::builtin` uses, just
+//! for TCP instead of UDP. They require `CAP_NET_RAW` (root).
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use rand::Rng;
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::proxy::ProxyConfig;
+use crate::proxy_protocol;
+use crate::scanner::results::PortStatus;
+
+const TCP_FLAG_FIN: u8 = 0x01;
+const TCP_FLAG_SYN: u8 = 0x02;
+const TCP_FLAG_RST: u8 = 0x04;
+const TCP_FLAG_PSH: u8 = 0x08;
+const TCP_FLAG_ACK: u8 = 0x10;
+const TCP_FLAG_URG: u8 = 0x20;
+
+/// Plain TCP connect scan. Optionally tunnels through a SOCKS5 proxy, and
+/// optionally sends a PROXY protocol v2 header right after connecting, for
+/// targets sitting behind a load balancer that requires one.
+pub async fn connect_scan(
+    target: IpAddr,
+    port: u16,
+    timeout_ms: u64,
+    proxy: Option<&ProxyConfig>,
+    send_proxy_header: bool,
+) -> PortStatus {
+    let duration = Duration::from_millis(timeout_ms);
+
+    match proxy {
+        Some(proxy) => connect_scan_via_proxy(target, port, duration, proxy).await,
+        None => {
+            let addr = SocketAddr::new(target, port);
+            match timeout(duration, TcpStream::connect(addr)).await {
+                Ok(Ok(mut stream)) => {
+                    if send_proxy_header {
+                        if let (Ok(local), Ok(peer)) = (stream.local_addr(), stream.peer_addr()) {
+                            // Best-effort: the connection is already open, so a
+                            // write failure here doesn't change the verdict.
+                            let _ = proxy_protocol::send_v2_header(&mut stream, local, peer).await;
+                        }
+                    }
+                    PortStatus::Open
+                }
+                Ok(Err(e)) if e.kind() == io::ErrorKind::ConnectionRefused => PortStatus::Closed,
+                Ok(Err(_)) => PortStatus::Filtered,
+                Err(_) => PortStatus::Filtered,
+            }
+        }
+    }
+}
+
+/// Same as `connect_scan`, just with a shorter timeout for hosts already
+/// known to be on the local network, where round trips are cheap.
+pub async fn fast_connect_scan(
+    target: IpAddr,
+    port: u16,
+    timeout_ms: u64,
+    proxy: Option<&ProxyConfig>,
+    send_proxy_header: bool,
+) -> PortStatus {
+    let fast_timeout = (timeout_ms / 4).max(50);
+    connect_scan(target, port, fast_timeout, proxy, send_proxy_header).await
+}
+
+/// Outcome of a `connect_scan_instrumented` probe. `kernel_rtt_ms`/
+/// `retransmits` come from the connected socket's `TCP_INFO` (Linux only)
+/// and, when present, are a strictly more accurate latency signal than
+/// timing the whole probe with a wall clock - that also counts scheduling
+/// and concurrency-limit wait, not just time on the wire.
+pub struct ConnectProbe {
+    pub status: PortStatus,
+    pub kernel_rtt_ms: Option<f64>,
+    pub retransmits: Option<u32>,
+}
+
+/// Same probe as `connect_scan`, additionally reading the kernel's
+/// `TCP_INFO` off the connected socket for an authoritative RTT/retransmit
+/// count in place of (or alongside) the caller's own wall-clock timing.
+pub async fn connect_scan_instrumented(
+    target: IpAddr,
+    port: u16,
+    timeout_ms: u64,
+    proxy: Option<&ProxyConfig>,
+    send_proxy_header: bool,
+) -> ConnectProbe {
+    if proxy.is_some() {
+        // TCP_INFO would describe our socket to the proxy, not the actual
+        // target, so there's nothing meaningful to read here.
+        let status = connect_scan(target, port, timeout_ms, proxy, send_proxy_header).await;
+        return ConnectProbe { status, kernel_rtt_ms: None, retransmits: None };
+    }
+
+    let duration = Duration::from_millis(timeout_ms);
+    let addr = SocketAddr::new(target, port);
+
+    match timeout(duration, TcpStream::connect(addr)).await {
+        Ok(Ok(mut stream)) => {
+            if send_proxy_header {
+                if let (Ok(local), Ok(peer)) = (stream.local_addr(), stream.peer_addr()) {
+                    let _ = proxy_protocol::send_v2_header(&mut stream, local, peer).await;
+                }
+            }
+            let (kernel_rtt_ms, retransmits) = match read_tcp_info(&stream) {
+                Some((rtt_ms, retransmits)) => (Some(rtt_ms), Some(retransmits)),
+                None => (None, None),
+            };
+            ConnectProbe { status: PortStatus::Open, kernel_rtt_ms, retransmits }
+        }
+        Ok(Err(e)) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            ConnectProbe { status: PortStatus::Closed, kernel_rtt_ms: None, retransmits: None }
+        }
+        Ok(Err(_)) => ConnectProbe { status: PortStatus::Filtered, kernel_rtt_ms: None, retransmits: None },
+        Err(_) => ConnectProbe { status: PortStatus::Filtered, kernel_rtt_ms: None, retransmits: None },
+    }
+}
+
+/// Same as `fast_connect_scan`, instrumented like `connect_scan_instrumented`.
+pub async fn fast_connect_scan_instrumented(
+    target: IpAddr,
+    port: u16,
+    timeout_ms: u64,
+    proxy: Option<&ProxyConfig>,
+    send_proxy_header: bool,
+) -> ConnectProbe {
+    let fast_timeout = (timeout_ms / 4).max(50);
+    connect_scan_instrumented(target, port, fast_timeout, proxy, send_proxy_header).await
+}
+
+/// Read `TCP_INFO` off a connected socket: smoothed RTT (converted from
+/// microseconds to milliseconds) and retransmit count. Linux-only - other
+/// platforms either lack the same sockopt or expose it under a different
+/// shape, and callers are expected to fall back to wall-clock timing when
+/// this returns `None`.
+#[cfg(target_os = "linux")]
+fn read_tcp_info(stream: &TcpStream) -> Option<(f64, u32)> {
+    use std::mem;
+    use std::os::unix::io::AsRawFd;
+
+    let fd = stream.as_raw_fd();
+    let mut info: libc::tcp_info = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<libc::tcp_info>() as libc::socklen_t;
+
+    let ret = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::IPPROTO_TCP,
+            libc::TCP_INFO,
+            &mut info as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret != 0 {
+        return None;
+    }
+
+    Some((info.tcpi_rtt as f64 / 1000.0, info.tcpi_retransmits as u32))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_tcp_info(_stream: &TcpStream) -> Option<(f64, u32)> {
+    None
+}
+
+async fn connect_scan_via_proxy(
+    target: IpAddr,
+    port: u16,
+    duration: Duration,
+    proxy: &ProxyConfig,
+) -> PortStatus {
+    use tokio_socks::tcp::Socks5Stream;
+
+    let result = match &proxy.credentials {
+        Some(creds) => {
+            let connect = Socks5Stream::connect_with_password(
+                proxy.addr,
+                (target, port),
+                &creds.username,
+                &creds.password,
+            );
+            timeout(duration, connect).await
+        }
+        None => {
+            let connect = Socks5Stream::connect(proxy.addr, (target, port));
+            timeout(duration, connect).await
+        }
+    };
+    match result {
+        Ok(Ok(_stream)) => PortStatus::Open,
+        // The proxy itself reports the far end refused the connection.
+        Ok(Err(tokio_socks::Error::ConnectionRefused)) => PortStatus::Closed,
+        Ok(Err(_)) => PortStatus::Filtered,
+        Err(_) => PortStatus::Filtered,
+    }
+}
+
+/// Probe a Unix domain socket path. There's no port concept here - a caller
+/// scanning a `unix:` target synthesizes a single port-0 `PortResult` from
+/// whatever status this returns.
+#[cfg(unix)]
+pub async fn unix_connect_scan(path: &str, timeout_ms: u64) -> PortStatus {
+    use tokio::net::UnixStream;
+
+    match timeout(Duration::from_millis(timeout_ms), UnixStream::connect(path)).await {
+        Ok(Ok(_stream)) => PortStatus::Open,
+        Ok(Err(e))
+            if e.kind() == io::ErrorKind::ConnectionRefused || e.kind() == io::ErrorKind::NotFound =>
+        {
+            PortStatus::Closed
+        }
+        Ok(Err(_)) => PortStatus::Filtered,
+        Err(_) => PortStatus::Filtered,
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn unix_connect_scan(_path: &str, _timeout_ms: u64) -> PortStatus {
+    PortStatus::Filtered
+}
+
+pub async fn syn_scan(target: IpAddr, port: u16, timeout_ms: u64) -> PortStatus {
+    raw_flag_scan(target, port, timeout_ms, TCP_FLAG_SYN, true).await
+}
+
+pub async fn fin_scan(target: IpAddr, port: u16, timeout_ms: u64) -> PortStatus {
+    raw_flag_scan(target, port, timeout_ms, TCP_FLAG_FIN, false).await
+}
+
+pub async fn xmas_scan(target: IpAddr, port: u16, timeout_ms: u64) -> PortStatus {
+    raw_flag_scan(target, port, timeout_ms, TCP_FLAG_FIN | TCP_FLAG_PSH | TCP_FLAG_URG, false).await
+}
+
+pub async fn null_scan(target: IpAddr, port: u16, timeout_ms: u64) -> PortStatus {
+    raw_flag_scan(target, port, timeout_ms, 0, false).await
+}
+
+/// Send a single bare TCP segment with `flags` set and classify the reply.
+/// `expects_syn_ack` distinguishes the SYN scan (where a SYN-ACK means open)
+/// from FIN/XMAS/NULL (where the only informative reply is an RST; silence
+/// is the expected behavior for an open port on a compliant stack, so we
+/// conservatively report it as filtered rather than guessing open).
+async fn raw_flag_scan(
+    target: IpAddr,
+    port: u16,
+    timeout_ms: u64,
+    flags: u8,
+    expects_syn_ack: bool,
+) -> PortStatus {
+    let IpAddr::V4(target_v4) = target else {
+        // Raw IPv4 headers only; IPv6 would need a parallel header layout.
+        return PortStatus::Filtered;
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        send_and_classify(target_v4, port, flags, timeout_ms, expects_syn_ack)
+    })
+    .await;
+
+    result.unwrap_or(PortStatus::Filtered)
+}
+
+fn send_and_classify(
+    target: Ipv4Addr,
+    port: u16,
+    flags: u8,
+    timeout_ms: u64,
+    expects_syn_ack: bool,
+) -> PortStatus {
+    let source_ip = match local_source_ip(target) {
+        Ok(ip) => ip,
+        Err(_) => return PortStatus::Filtered,
+    };
+
+    let socket = match Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::TCP)) {
+        Ok(socket) => socket,
+        // Most likely not running with CAP_NET_RAW.
+        Err(_) => return PortStatus::Filtered,
+    };
+    if socket.set_read_timeout(Some(Duration::from_millis(timeout_ms))).is_err() {
+        return PortStatus::Filtered;
+    }
+
+    let src_port: u16 = rand::thread_rng().gen_range(49152..65535);
+    let seq: u32 = rand::thread_rng().gen();
+    let segment = build_tcp_segment(source_ip, target, src_port, port, seq, flags);
+
+    let dest = SockAddr::from(SocketAddr::new(IpAddr::V4(target), port));
+    if socket.send_to(&segment, &dest).is_err() {
+        return PortStatus::Filtered;
+    }
+
+    let mut buf = [0u8; 1500];
+    loop {
+        match socket.recv(&mut buf) {
+            Ok(n) => match classify_reply(&buf[..n], src_port, port, expects_syn_ack) {
+                Some(status) => return status,
+                // Not our reply (e.g. unrelated traffic on the raw socket); keep reading.
+                None => continue,
+            },
+            Err(e)
+                if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut =>
+            {
+                return PortStatus::Filtered;
+            }
+            Err(_) => return PortStatus::Filtered,
+        }
+    }
+}
+
+/// Determine the local interface address the kernel would use to reach
+/// `target`, without actually sending anything (UDP `connect` just does a
+/// route lookup).
+fn local_source_ip(target: Ipv4Addr) -> io::Result<Ipv4Addr> {
+    let udp = UdpSocket::bind("0.0.0.0:0")?;
+    udp.connect((target, 0))?;
+    match udp.local_addr()?.ip() {
+        IpAddr::V4(ip) => Ok(ip),
+        IpAddr::V6(_) => Ok(Ipv4Addr::UNSPECIFIED),
+    }
+}
+
+fn classify_reply(buf: &[u8], src_port: u16, dst_port: u16, expects_syn_ack: bool) -> Option<PortStatus> {
+    // Skip the IPv4 header (variable length, IHL in the low nibble of byte 0).
+    if buf.len() < 20 {
+        return None;
+    }
+    let ihl = ((buf[0] & 0x0f) as usize) * 4;
+    if buf.len() < ihl + 20 {
+        return None;
+    }
+    let tcp = &buf[ihl..];
+
+    let reply_src_port = u16::from_be_bytes([tcp[0], tcp[1]]);
+    let reply_dst_port = u16::from_be_bytes([tcp[2], tcp[3]]);
+    if reply_src_port != dst_port || reply_dst_port != src_port {
+        return None;
+    }
+
+    let reply_flags = tcp[13];
+    if reply_flags & TCP_FLAG_RST != 0 {
+        return Some(PortStatus::Closed);
+    }
+    if expects_syn_ack && reply_flags & (TCP_FLAG_SYN | TCP_FLAG_ACK) == (TCP_FLAG_SYN | TCP_FLAG_ACK) {
+        return Some(PortStatus::Open);
+    }
+
+    Some(PortStatus::Filtered)
+}
+
+/// Build a bare IPv4 packet carrying a 20-byte TCP segment (no options) with
+/// the given flags, with both checksums filled in.
+fn build_tcp_segment(
+    source: Ipv4Addr,
+    dest: Ipv4Addr,
+    src_port: u16,
+    dst_port: u16,
+    seq: u32,
+    flags: u8,
+) -> Vec<u8> {
+    let mut tcp = vec![0u8; 20];
+    tcp[0..2].copy_from_slice(&src_port.to_be_bytes());
+    tcp[2..4].copy_from_slice(&dst_port.to_be_bytes());
+    tcp[4..8].copy_from_slice(&seq.to_be_bytes());
+    tcp[8..12].copy_from_slice(&0u32.to_be_bytes()); // ack number, unused
+    tcp[12] = 5 << 4; // data offset: 5 32-bit words, no options
+    tcp[13] = flags;
+    tcp[14..16].copy_from_slice(&4096u16.to_be_bytes()); // window
+    tcp[16..18].copy_from_slice(&0u16.to_be_bytes()); // checksum, filled below
+    tcp[18..20].copy_from_slice(&0u16.to_be_bytes()); // urgent pointer, unused
+
+    let tcp_checksum = checksum_with_pseudo_header(source, dest, &tcp);
+    tcp[16..18].copy_from_slice(&tcp_checksum.to_be_bytes());
+
+    let mut ip = vec![0u8; 20];
+    ip[0] = 0x45; // version 4, 5 32-bit words of header
+    ip[1] = 0; // DSCP/ECN
+    let total_len = (ip.len() + tcp.len()) as u16;
+    ip[2..4].copy_from_slice(&total_len.to_be_bytes());
+    ip[4..6].copy_from_slice(&rand::thread_rng().gen::<u16>().to_be_bytes()); // identification
+    ip[6..8].copy_from_slice(&0u16.to_be_bytes()); // flags/fragment offset
+    ip[8] = 64; // TTL
+    ip[9] = 6; // protocol: TCP
+    ip[10..12].copy_from_slice(&0u16.to_be_bytes()); // checksum, filled below
+    ip[12..16].copy_from_slice(&source.octets());
+    ip[16..20].copy_from_slice(&dest.octets());
+
+    let ip_checksum = checksum(&ip);
+    ip[10..12].copy_from_slice(&ip_checksum.to_be_bytes());
+
+    ip.extend_from_slice(&tcp);
+    ip
+}
+
+/// RFC 793 TCP checksum, computed over a pseudo-header (source/dest IP,
+/// zero byte, protocol, TCP length) followed by the segment itself.
+fn checksum_with_pseudo_header(source: Ipv4Addr, dest: Ipv4Addr, tcp_segment: &[u8]) -> u16 {
+    let mut pseudo = Vec::with_capacity(12 + tcp_segment.len());
+    pseudo.extend_from_slice(&source.octets());
+    pseudo.extend_from_slice(&dest.octets());
+    pseudo.push(0);
+    pseudo.push(6); // protocol: TCP
+    pseudo.extend_from_slice(&(tcp_segment.len() as u16).to_be_bytes());
+    pseudo.extend_from_slice(tcp_segment);
+    checksum(&pseudo)
+}
+
+/// Standard Internet checksum (RFC 1071): one's-complement sum of 16-bit
+/// words, folded down to 16 bits and complemented.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = 0u32;
+    let mut chunks = data.chunks_exact(2);
+    for chunk in &mut chunks {
+        sum += u16::from_be_bytes([chunk[0], chunk[1]]) as u32;
+    }
+    if let [last] = *chunks.remainder() {
+        sum += (last as u32) << 8;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_of_empty_is_all_ones() {
+        assert_eq!(checksum(&[]), 0xFFFF);
+    }
+
+    #[test]
+    fn test_build_tcp_segment_has_correct_lengths() {
+        let packet = build_tcp_segment(
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            12345,
+            80,
+            1,
+            TCP_FLAG_SYN,
+        );
+        assert_eq!(packet.len(), 40);
+    }
+}
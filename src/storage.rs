@@ -0,0 +1,366 @@
+//! SQLite-backed persistence for scan results and adaptive intelligence.
+//!
+//! `StorageConfig::backend` can select `Json`/`Memory`/`Sqlite`, but only this
+//! module actually persists anything durable: scan results and per-port/per-host
+//! intelligence land in normalized tables (rather than one JSON blob per run) so
+//! the adaptive subsystem can do indexed `(ip, port)` lookups instead of
+//! deserializing and scanning every historical record. Rooted at
+//! `Config::get_data_dir()`, in a `scans.db` file opened with WAL journaling so
+//! reads during a long scan don't block the writer.
+
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteJournalMode};
+use sqlx::SqlitePool;
+
+use crate::config::{Config, StorageConfig};
+
+/// One historical observation of a single port, as persisted to
+/// `port_intelligence`. Kept independent of the in-memory adaptive-learning
+/// types so this module has no dependency on how that subsystem shapes data.
+#[derive(Debug, Clone)]
+pub struct PortHistoryEntry {
+    pub scanned_at: DateTime<Utc>,
+    pub is_open: bool,
+    pub response_time_ms: Option<f64>,
+    pub service_name: Option<String>,
+}
+
+/// SQLite-backed persistence layer rooted at the configured data directory.
+pub struct SqliteStorage {
+    pool: SqlitePool,
+    db_path: PathBuf,
+    enable_compression: bool,
+    max_file_size_mb: u32,
+}
+
+impl SqliteStorage {
+    /// Open (creating if necessary) `scans.db` under `config.get_data_dir()`
+    /// and ensure the schema exists.
+    pub async fn open(config: &Config) -> Result<Self> {
+        let mut db_path = config.get_data_dir();
+        std::fs::create_dir_all(&db_path)?;
+        db_path.push("scans.db");
+
+        let storage = Self::open_at(&db_path, &config.storage).await?;
+        Ok(storage)
+    }
+
+    /// Open a store at an explicit path, bypassing `Config::get_data_dir()`.
+    /// Exposed mainly so tests can point at a temp file without touching the
+    /// real config directory.
+    pub async fn open_at(db_path: &Path, config: &StorageConfig) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+
+        let storage = Self {
+            pool,
+            db_path: db_path.to_path_buf(),
+            enable_compression: config.enable_compression,
+            max_file_size_mb: config.max_file_size_mb,
+        };
+
+        storage.run_migrations().await?;
+        Ok(storage)
+    }
+
+    async fn run_migrations(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS scan_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                ip TEXT NOT NULL,
+                port INTEGER NOT NULL,
+                is_open INTEGER NOT NULL,
+                response_time_ms REAL,
+                service_name TEXT,
+                banner BLOB,
+                banner_compressed INTEGER NOT NULL DEFAULT 0,
+                scanned_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_scan_results_ip_port ON scan_results (ip, port)",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS port_intelligence (
+                ip TEXT NOT NULL,
+                port INTEGER NOT NULL,
+                avg_response_time_ms REAL NOT NULL,
+                open_count INTEGER NOT NULL DEFAULT 0,
+                closed_count INTEGER NOT NULL DEFAULT 0,
+                filtered_count INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (ip, port)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS host_intelligence (
+                ip TEXT PRIMARY KEY,
+                optimal_timeout_ms INTEGER NOT NULL,
+                optimal_rate_limit_ms INTEGER NOT NULL,
+                optimal_parallelism INTEGER NOT NULL,
+                scan_count INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Persist one port observation from a completed scan, optionally
+    /// compressing the banner blob per `StorageConfig::enable_compression`.
+    pub async fn record_port_result(
+        &self,
+        ip: IpAddr,
+        port: u16,
+        is_open: bool,
+        response_time_ms: Option<f64>,
+        service_name: Option<&str>,
+        banner: Option<&[u8]>,
+    ) -> Result<()> {
+        let (banner, compressed) = match banner {
+            Some(bytes) if self.enable_compression => (Some(compress(bytes)?), true),
+            Some(bytes) => (Some(bytes.to_vec()), false),
+            None => (None, false),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO scan_results
+                (ip, port, is_open, response_time_ms, service_name, banner, banner_compressed, scanned_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(ip.to_string())
+        .bind(port as i64)
+        .bind(is_open)
+        .bind(response_time_ms)
+        .bind(service_name)
+        .bind(banner)
+        .bind(compressed)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Upsert the rolling per-`(ip, port)` intelligence used to seed future
+    /// scans' expected timeout/response-time.
+    pub async fn upsert_port_intelligence(
+        &self,
+        ip: IpAddr,
+        port: u16,
+        avg_response_time_ms: f64,
+        is_open: bool,
+        is_filtered: bool,
+    ) -> Result<()> {
+        let ip_str = ip.to_string();
+        let (open_inc, closed_inc, filtered_inc) = match (is_open, is_filtered) {
+            (true, _) => (1, 0, 0),
+            (false, true) => (0, 0, 1),
+            (false, false) => (0, 1, 0),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO port_intelligence (ip, port, avg_response_time_ms, open_count, closed_count, filtered_count, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(ip, port) DO UPDATE SET
+                avg_response_time_ms = (avg_response_time_ms + excluded.avg_response_time_ms) / 2.0,
+                open_count = open_count + excluded.open_count,
+                closed_count = closed_count + excluded.closed_count,
+                filtered_count = filtered_count + excluded.filtered_count,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&ip_str)
+        .bind(port as i64)
+        .bind(avg_response_time_ms)
+        .bind(open_inc)
+        .bind(closed_inc)
+        .bind(filtered_inc)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Indexed lookup of recent history for a single `(ip, port)`, newest first.
+    pub async fn get_port_history(
+        &self,
+        ip: IpAddr,
+        port: u16,
+        limit: i64,
+    ) -> Result<Vec<PortHistoryEntry>> {
+        let rows: Vec<(String, bool, Option<f64>, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT scanned_at, is_open, response_time_ms, service_name
+            FROM scan_results
+            WHERE ip = ? AND port = ?
+            ORDER BY scanned_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(ip.to_string())
+        .bind(port as i64)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(scanned_at, is_open, response_time_ms, service_name)| {
+                Ok(PortHistoryEntry {
+                    scanned_at: DateTime::parse_from_rfc3339(&scanned_at)?.with_timezone(&Utc),
+                    is_open,
+                    response_time_ms,
+                    service_name,
+                })
+            })
+            .collect()
+    }
+
+    /// Delete rows older than `data_retention_days` (from `AdaptiveConfig`)
+    /// across every table with a timestamp. Returns the number of rows removed.
+    pub async fn purge_expired(&self, data_retention_days: u32) -> Result<u64> {
+        let cutoff = (Utc::now() - chrono::Duration::days(data_retention_days as i64)).to_rfc3339();
+
+        let scan_results = sqlx::query("DELETE FROM scan_results WHERE scanned_at < ?")
+            .bind(&cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        let port_intelligence = sqlx::query("DELETE FROM port_intelligence WHERE updated_at < ?")
+            .bind(&cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        let host_intelligence = sqlx::query("DELETE FROM host_intelligence WHERE updated_at < ?")
+            .bind(&cutoff)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(scan_results.rows_affected()
+            + port_intelligence.rows_affected()
+            + host_intelligence.rows_affected())
+    }
+
+    /// Reclaim space with `VACUUM` once the on-disk file exceeds
+    /// `StorageConfig::max_file_size_mb`. Cheap no-op otherwise.
+    pub async fn vacuum_if_needed(&self) -> Result<bool> {
+        let size_mb = std::fs::metadata(&self.db_path)?.len() / (1024 * 1024);
+        if size_mb >= self.max_file_size_mb as u64 {
+            sqlx::query("VACUUM").execute(&self.pool).await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+fn compress(data: &[u8]) -> Result<Vec<u8>> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_storage() -> SqliteStorage {
+        SqliteStorage::open_at(Path::new(":memory:"), &StorageConfig {
+            backend: crate::config::StorageBackend::Sqlite,
+            data_dir: None,
+            enable_compression: true,
+            backup_enabled: false,
+            max_file_size_mb: 100,
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_and_query_port_history() {
+        let storage = test_storage().await;
+        let ip: IpAddr = "192.168.1.1".parse().unwrap();
+
+        storage
+            .record_port_result(ip, 80, true, Some(12.5), Some("http"), Some(b"Server: nginx"))
+            .await
+            .unwrap();
+
+        let history = storage.get_port_history(ip, 80, 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].is_open);
+        assert_eq!(history[0].service_name.as_deref(), Some("http"));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_port_intelligence_accumulates_counts() {
+        let storage = test_storage().await;
+        let ip: IpAddr = "10.0.0.5".parse().unwrap();
+
+        storage.upsert_port_intelligence(ip, 22, 5.0, true, false).await.unwrap();
+        storage.upsert_port_intelligence(ip, 22, 7.0, true, false).await.unwrap();
+
+        let row: (f64, i64) = sqlx::query_as(
+            "SELECT avg_response_time_ms, open_count FROM port_intelligence WHERE ip = ? AND port = ?",
+        )
+        .bind(ip.to_string())
+        .bind(22i64)
+        .fetch_one(&storage.pool)
+        .await
+        .unwrap();
+
+        assert_eq!(row.1, 2);
+        assert!(row.0 > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_removes_old_rows() {
+        let storage = test_storage().await;
+        let ip: IpAddr = "127.0.0.1".parse().unwrap();
+        storage.record_port_result(ip, 443, false, None, None, None).await.unwrap();
+
+        // Everything is brand new, so a 0-day retention window should purge it.
+        let deleted = storage.purge_expired(0).await.unwrap();
+        assert!(deleted >= 1);
+    }
+}